@@ -7,6 +7,7 @@ pub struct ActionBundle {
     current_action: Action,
     action_timer: ActionTimer,
     action_duration: ActionDurationPHF,
+    motion: Motion,
 }
 
 impl ActionBundle {
@@ -14,8 +15,9 @@ impl ActionBundle {
     pub fn new(default_action: &Action, action_duration_map: ActionDurationPHF) -> Self {
         Self {
             current_action: default_action.clone(),
-            action_timer: action_duration_map.generate_timer(default_action),
+            action_timer: action_duration_map.generate_timer(default_action, 1.),
             action_duration: action_duration_map,
+            motion: Motion::default(),
         }
     }
 }
@@ -0,0 +1,45 @@
+use bevy::prelude::*;
+
+use crate::components::brain::{BehaviorTreeBrain, OfflineBrain};
+use crate::components::command::ActionQueue;
+use crate::components::gpt::BotDefinition;
+use crate::components::interaction::Interactable;
+use crate::constants::action::BOT_ACTION_DURATION_MAP;
+
+use super::mob::PlayerMobBundle;
+
+/// Bundle for creating a bot driven by its offline brain.
+#[derive(Bundle)]
+pub struct BotBundle {
+    player_mob: PlayerMobBundle,
+    name: Name,
+    action_queue: ActionQueue,
+    brain: OfflineBrain,
+    interactable: Interactable,
+}
+
+impl BotBundle {
+    /// Creates a bot, its brain is seeded from its name.
+    pub fn new(
+        definition: &BotDefinition,
+        texture: &Handle<Image>,
+        texture_atlas: &mut ResMut<Assets<TextureAtlas>>,
+        action_queue: ActionQueue,
+    ) -> Self {
+        let seed = definition.name.bytes().fold(0u64, |hash, byte| {
+            hash.wrapping_mul(31).wrapping_add(byte as u64)
+        });
+        Self {
+            player_mob: PlayerMobBundle::new(
+                definition.spawn,
+                texture,
+                texture_atlas,
+                BOT_ACTION_DURATION_MAP,
+            ),
+            name: Name::new(definition.name),
+            action_queue,
+            brain: OfflineBrain::new(BehaviorTreeBrain::new(seed)),
+            interactable: Interactable::Talk,
+        }
+    }
+}
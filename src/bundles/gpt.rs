@@ -1,33 +1,34 @@
 use bevy::prelude::*;
 
-use crate::components::gpt::GPTAgent;
+use crate::components::command::ActionQueue;
+use crate::components::gpt::{BotDefinition, GPTAgent};
 use crate::constants::bot::*;
 
-use super::mob::PlayerMobBundle;
+use super::bot::BotBundle;
 
 /// Bundle for creating a GPT agent.
+///
+/// The offline brain takes over while the model is unavailable.
 #[derive(Bundle)]
 pub struct GptBundle {
-    player_mob: PlayerMobBundle,
+    bot: BotBundle,
     agent: GPTAgent,
 }
 
 impl GptBundle {
     pub fn new(
-        position: Vec2,
+        definition: &BotDefinition,
         texture: &Handle<Image>,
         texture_atlas: &mut ResMut<Assets<TextureAtlas>>,
         key: String,
         model: String,
         url: String,
     ) -> Option<Self> {
-        GPTAgent::new(key, model, url).map(|mut agent| {
-            agent.add_context(CONTEXT);
-            agent.add_context(COMMANDS);
-            GptBundle {
-                player_mob: PlayerMobBundle::new(position, texture, texture_atlas),
-                agent,
-            }
+        let system_prompt = [definition.persona, CONTEXT, COMMANDS].join("\n");
+        let action_queue = ActionQueue::default();
+        GPTAgent::new(key, model, url, system_prompt, action_queue.clone()).map(|agent| GptBundle {
+            bot: BotBundle::new(definition, texture, texture_atlas, action_queue),
+            agent,
         })
     }
 }
@@ -0,0 +1,61 @@
+use bevy::prelude::*;
+
+use crate::components::interaction::Interactable;
+use crate::constants::interaction::*;
+use crate::constants::map::TILE;
+
+/// Bundle for creating an interactable object lying on a tile.
+#[derive(Bundle)]
+pub struct InteractableBundle {
+    sprite: SpriteBundle,
+    interactable: Interactable,
+    name: Name,
+}
+
+impl InteractableBundle {
+    /// Creates a sign showing a text.
+    pub fn sign(tile_pos: IVec2, text: &str) -> Self {
+        Self::new(
+            tile_pos,
+            Interactable::Sign(text.into()),
+            "Sign",
+            SIGN_COLOR,
+            SIGN_SIZE,
+        )
+    }
+
+    /// Creates a closed chest.
+    pub fn chest(tile_pos: IVec2) -> Self {
+        Self::new(
+            tile_pos,
+            Interactable::Chest { is_open: false },
+            "Chest",
+            CHEST_COLOR,
+            CHEST_SIZE,
+        )
+    }
+
+    fn new(
+        tile_pos: IVec2,
+        interactable: Interactable,
+        name: &str,
+        color: Color,
+        size: Vec2,
+    ) -> Self {
+        Self {
+            sprite: SpriteBundle {
+                sprite: Sprite {
+                    color,
+                    custom_size: Some(size),
+                    ..Default::default()
+                },
+                transform: Transform::from_translation(
+                    (tile_pos.as_vec2() * TILE).extend(INTERACTABLE_LAYER),
+                ),
+                ..Default::default()
+            },
+            interactable,
+            name: Name::new(name.to_string()),
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use bevy::prelude::*;
+
+use crate::components::item::{ItemDrop, ItemStack};
+use crate::constants::item::*;
+use crate::constants::map::TILE;
+
+/// Bundle for creating items lying on a tile.
+#[derive(Bundle)]
+pub struct ItemDropBundle {
+    sprite: SpriteSheetBundle,
+    item_drop: ItemDrop,
+}
+
+impl ItemDropBundle {
+    /// Creates an item drop, drawn with the sprite of its definition.
+    pub fn new(
+        stack: ItemStack,
+        tile_pos: IVec2,
+        texture: &Handle<Image>,
+        texture_atlas: &mut ResMut<Assets<TextureAtlas>>,
+    ) -> Self {
+        let sprite_index = ITEM_REGISTRY
+            .get(stack.id)
+            .map_or(0, |definition| definition.sprite_index);
+        Self {
+            sprite: SpriteSheetBundle {
+                sprite: TextureAtlasSprite::new(sprite_index),
+                texture_atlas: texture_atlas.add(ITEM_SPRITE_GRID.to_atlas(texture.clone())),
+                transform: Transform::from_translation(
+                    (tile_pos.as_vec2() * TILE).extend(ITEM_DROP_LAYER),
+                ),
+                ..Default::default()
+            },
+            item_drop: ItemDrop(stack),
+        }
+    }
+}
@@ -4,8 +4,10 @@ use crate::components::action::*;
 use crate::components::animation::ActionAnimationMap;
 use crate::components::animation::AnimationSpriteGrid;
 use crate::components::character::*;
+use crate::components::item::Inventory;
 use crate::components::map::ChunkMap;
 use crate::components::texture::TilesetOffset;
+use crate::components::wildlife::Creature;
 use crate::constants::action::*;
 use crate::constants::character::*;
 use crate::constants::map::TILE;
@@ -21,15 +23,28 @@ use super::animation::AnimationBundle;
 pub struct MobBundle {
     busy: Busy,
     health: Health,
+    max_health: MaxHealth,
+    spawn_point: SpawnPoint,
+    footing: Footing,
+    submersion: Submersion,
+    inventory: Inventory,
     action: ActionBundle,
-    chunk_map: ChunkMap,
     animation: ActionAnimationBundle,
 }
 
-/// Bundle for creating a player mob in the game.
+/// Bundle for creating a player mob in the game, loading the chunks around it.
 #[derive(Bundle)]
 pub struct PlayerMobBundle {
     mob: MobBundle,
+    chunk_map: ChunkMap,
+}
+
+/// Bundle for creating a wildlife creature, living in the chunks loaded by players.
+#[derive(Bundle)]
+pub struct CreatureMobBundle {
+    mob: MobBundle,
+    name: Name,
+    creature: Creature,
 }
 
 /// Texture-related parameters
@@ -62,12 +77,16 @@ impl MobBundle {
     ) -> Self {
         Self {
             busy: Busy(AtomicBool::new(false)),
+            max_health: MaxHealth(*spawn_params.health),
             health: spawn_params.health,
+            spawn_point: SpawnPoint(*spawn_params.position),
+            footing: Footing::default(),
+            submersion: Submersion::default(),
+            inventory: Inventory::default(),
             action: ActionBundle::new(
                 action_params.default_action,
                 action_params.action_duration_map,
             ),
-            chunk_map: ChunkMap::new(),
             animation: ActionAnimationBundle {
                 animation_bundle: AnimationBundle::new(
                     Vec3::new(
@@ -94,6 +113,7 @@ impl PlayerMobBundle {
         position: Vec2,
         texture: &Handle<Image>,
         texture_atlas: &mut ResMut<Assets<TextureAtlas>>,
+        action_duration_map: ActionDurationPHF,
     ) -> Self {
         let spawn_params = SpawnParameters {
             position: &position,
@@ -109,12 +129,48 @@ impl PlayerMobBundle {
 
         let action_params = ActionParameters {
             default_action: &PLAYER_ACTION_DEFAULT,
-            action_duration_map: PLAYER_ACTION_DURATION_MAP,
+            action_duration_map,
             action_animation_map: &PLAYER_SPRITE_INDICES_MAP,
         };
 
         Self {
             mob: MobBundle::new(spawn_params, texture_params, action_params),
+            chunk_map: ChunkMap::new(),
+        }
+    }
+}
+
+impl CreatureMobBundle {
+    /// Creates a new creature mob bundle with the specified parameters.
+    pub fn new(
+        position: Vec2,
+        creature: Creature,
+        texture: &Handle<Image>,
+        texture_atlas: &mut ResMut<Assets<TextureAtlas>>,
+    ) -> Self {
+        let species = creature.species;
+        let spawn_params = SpawnParameters {
+            position: &position,
+            layer: CREATURE_SPRITE_LAYER,
+            health: Health(species.health),
+        };
+
+        let texture_params = TextureParameters {
+            texture,
+            texture_atlas,
+            sprite_grid: species.sprite_grid.clone(),
+        };
+
+        let action_params = ActionParameters {
+            default_action: &PLAYER_ACTION_DEFAULT,
+            action_duration_map: CREATURE_ACTION_DURATION_MAP,
+            action_animation_map: species.animations,
+        };
+
+        Self {
+            mob: MobBundle::new(spawn_params, texture_params, action_params),
+            name: Name::new(species.name),
+            creature,
         }
     }
 }
@@ -5,12 +5,20 @@ pub mod animation;
 // Action bundle (abstract)
 pub mod action;
 // Bot entities.
+pub mod bot;
+// GPT bot entities.
 pub mod gpt;
+// Interactable objects
+pub mod interactable;
+// Items lying in the world
+pub mod item;
 // Map & chunk, tile entities
 pub mod map;
 // Living mob
 pub mod mob;
 // Player entities
 pub mod player;
+// Pieces of generated structures
+pub mod structure;
 // Effect
 pub mod effect;
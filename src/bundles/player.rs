@@ -1,10 +1,19 @@
 use bevy::prelude::*;
 
+use crate::components::character::{MovementMode, Velocity};
+use crate::components::command::ActionQueue;
+use crate::constants::action::PLAYER_ACTION_DURATION_MAP;
+use crate::constants::character::USER_NAME;
+
 use super::mob::PlayerMobBundle;
 
 #[derive(Bundle)]
 pub struct PlayerBundle {
     player_mob: PlayerMobBundle,
+    name: Name,
+    movement_mode: MovementMode,
+    velocity: Velocity,
+    action_queue: ActionQueue,
 }
 
 impl PlayerBundle {
@@ -15,7 +24,16 @@ impl PlayerBundle {
         texture_atlas: &mut ResMut<Assets<TextureAtlas>>,
     ) -> Self {
         Self {
-            player_mob: PlayerMobBundle::new(position, texture, texture_atlas),
+            player_mob: PlayerMobBundle::new(
+                position,
+                texture,
+                texture_atlas,
+                PLAYER_ACTION_DURATION_MAP,
+            ),
+            name: Name::new(USER_NAME),
+            movement_mode: MovementMode::default(),
+            velocity: Velocity::default(),
+            action_queue: ActionQueue::default(),
         }
     }
 }
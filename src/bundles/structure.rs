@@ -0,0 +1,40 @@
+use bevy::prelude::*;
+
+use crate::components::structure::StructurePiece;
+use crate::constants::map::TILE;
+use crate::constants::structure::*;
+
+/// Bundle for creating a structure piece blocking its tile, signs are interactable objects.
+#[derive(Bundle)]
+pub struct StructurePieceBundle {
+    sprite: SpriteBundle,
+    piece: StructurePiece,
+    name: Name,
+}
+
+impl StructurePieceBundle {
+    /// Creates a structure piece lying on a tile.
+    pub fn new(piece: StructurePiece, tile_pos: IVec2) -> Self {
+        let (name, color, size) = match piece {
+            StructurePiece::Wall => ("Wall", WALL_COLOR, WALL_SIZE),
+            StructurePiece::House => ("House", HOUSE_COLOR, HOUSE_SIZE),
+            StructurePiece::Campfire => ("Campfire", CAMPFIRE_COLOR, CAMPFIRE_SIZE),
+            StructurePiece::Sign => ("Sign", WALL_COLOR, WALL_SIZE),
+        };
+        Self {
+            sprite: SpriteBundle {
+                sprite: Sprite {
+                    color,
+                    custom_size: Some(size),
+                    ..Default::default()
+                },
+                transform: Transform::from_translation(
+                    (tile_pos.as_vec2() * TILE).extend(STRUCTURE_LAYER),
+                ),
+                ..Default::default()
+            },
+            piece,
+            name: Name::new(name),
+        }
+    }
+}
@@ -1,14 +1,16 @@
 use bevy::prelude::*;
 use phf::Map;
 use serde::Deserialize;
-use std::str::FromStr;
-use strum_macros::{Display, EnumString};
+use strum_macros::{Display, EnumIter, EnumString};
 
+use crate::components::map::TileKind;
 use crate::constants::action::*;
 use crate::constants::map::TILE;
 
-/// Represents the direction of an action (e.g., Up, Down, Left, Right).
-#[derive(Component, Debug, Clone, PartialEq, Eq, Hash, Display, Deserialize, EnumString)]
+/// Represents the direction of an action (e.g., Up, Down, Left, UpRight).
+#[derive(
+    Component, Debug, Clone, PartialEq, Eq, Hash, Display, Deserialize, EnumString, EnumIter,
+)]
 pub enum ActionDirection {
     #[strum(ascii_case_insensitive)]
     Up,
@@ -18,6 +20,26 @@ pub enum ActionDirection {
     Left,
     #[strum(ascii_case_insensitive)]
     Right,
+    #[strum(serialize = "UpLeft", serialize = "up-left", serialize = "up_left")]
+    #[strum(ascii_case_insensitive)]
+    UpLeft,
+    #[strum(serialize = "UpRight", serialize = "up-right", serialize = "up_right")]
+    #[strum(ascii_case_insensitive)]
+    UpRight,
+    #[strum(
+        serialize = "DownLeft",
+        serialize = "down-left",
+        serialize = "down_left"
+    )]
+    #[strum(ascii_case_insensitive)]
+    DownLeft,
+    #[strum(
+        serialize = "DownRight",
+        serialize = "down-right",
+        serialize = "down_right"
+    )]
+    #[strum(ascii_case_insensitive)]
+    DownRight,
 }
 
 /// Represents the kind of an action (e.g., Stand, Walk, Run).
@@ -31,6 +53,17 @@ pub enum ActionKind {
     Run,
     #[strum(ascii_case_insensitive)]
     Type,
+    #[strum(ascii_case_insensitive)]
+    Swim,
+    #[strum(ascii_case_insensitive)]
+    Interact,
+    #[strum(ascii_case_insensitive)]
+    Attack,
+    #[strum(ascii_case_insensitive)]
+    Block,
+    /// Forced move away from an attacker.
+    #[strum(ascii_case_insensitive)]
+    Knockback,
     // Add future actions here
 }
 
@@ -58,59 +91,64 @@ impl Action {
         let norm = match self.kind {
             ActionKind::Walk => WALK_RATE as i32,
             ActionKind::Run => RUN_RATE as i32,
+            ActionKind::Swim => SWIM_RATE as i32,
+            ActionKind::Knockback => KNOCKBACK_RATE as i32,
             _ => 0_i32,
         };
 
-        match self.direction {
-            ActionDirection::Up => IVec2::new(0, norm),
-            ActionDirection::Down => IVec2::new(0, -norm),
-            ActionDirection::Left => IVec2::new(-norm, 0),
-            ActionDirection::Right => IVec2::new(norm, 0),
-        }
+        let vector = self.direction.to_vector();
+        IVec2::new(vector.x * norm, vector.y * norm)
     }
+}
 
-    /// Parses a command string and returns a vector of actions.
-    ///
-    /// # Arguments
-    /// * `commands` - A command string containing one or more actions.
-    ///
-    /// # Returns
-    /// A `Vec` of `Action` instances parsed from the command string.
-    pub fn from_command_string(commands: &str) -> Option<Vec<Action>> {
-        let uppercase = commands.to_uppercase();
-        let mut actions = Vec::new();
-        for line in uppercase.lines() {
-            if let Some(mut line_actions) = Self::from_single_command_string(line) {
-                actions.append(&mut line_actions);
-            }
-        }
-        if actions.is_empty() {
-            None
-        } else {
-            Some(actions)
+impl ActionDirection {
+    /// Unit tile vector of the direction, diagonals move on both axes.
+    pub const fn to_vector(&self) -> IVec2 {
+        match self {
+            ActionDirection::Up => IVec2::new(0, 1),
+            ActionDirection::Down => IVec2::new(0, -1),
+            ActionDirection::Left => IVec2::new(-1, 0),
+            ActionDirection::Right => IVec2::new(1, 0),
+            ActionDirection::UpLeft => IVec2::new(-1, 1),
+            ActionDirection::UpRight => IVec2::new(1, 1),
+            ActionDirection::DownLeft => IVec2::new(-1, -1),
+            ActionDirection::DownRight => IVec2::new(1, -1),
         }
     }
 
-    fn from_single_command_string(command: &str) -> Option<Vec<Action>> {
-        let parts: Vec<&str> = command.split_whitespace().collect();
-        if parts.len() < 2 || parts.len() > 3 {
-            return None;
+    /// Finds the direction of a vector, only its signs are used.
+    ///
+    /// Returns `None` for a null vector.
+    pub fn from_vector(vector: IVec2) -> Option<Self> {
+        match (vector.x.signum(), vector.y.signum()) {
+            (0, 1) => Some(ActionDirection::Up),
+            (0, -1) => Some(ActionDirection::Down),
+            (-1, 0) => Some(ActionDirection::Left),
+            (1, 0) => Some(ActionDirection::Right),
+            (-1, 1) => Some(ActionDirection::UpLeft),
+            (1, 1) => Some(ActionDirection::UpRight),
+            (-1, -1) => Some(ActionDirection::DownLeft),
+            (1, -1) => Some(ActionDirection::DownRight),
+            _ => None,
         }
+    }
 
-        let mut kind = ActionKind::from_str(parts[0]).ok()?;
-        let direction = ActionDirection::from_str(parts[1]).ok()?;
+    pub fn opposite(&self) -> Self {
+        Self::from_vector(-self.to_vector()).expect("Directions are never null!")
+    }
 
-        let times = if parts.len() == 3 {
-            parts[2].parse::<usize>().ok()?
-        } else {
-            1
-        };
+    pub const fn is_diagonal(&self) -> bool {
+        let vector = self.to_vector();
+        vector.x != 0 && vector.y != 0
+    }
 
-        if times > 5 && kind == ActionKind::Walk {
-            kind = ActionKind::Run
+    /// Horizontal part of a diagonal, other directions are returned as is.
+    pub fn horizontal(&self) -> Self {
+        match self {
+            ActionDirection::UpLeft | ActionDirection::DownLeft => ActionDirection::Left,
+            ActionDirection::UpRight | ActionDirection::DownRight => ActionDirection::Right,
+            direction => direction.clone(),
         }
-
-        Some(vec![Action { kind, direction }; times])
     }
 }
 
@@ -118,10 +156,24 @@ impl Action {
 #[derive(Component, Deref, DerefMut)]
 pub struct ActionTimer(pub Timer);
 
+/// Fixed-timestep movement of an entity going from a tile to another.
+#[derive(Component, Default)]
+pub struct Motion {
+    /// Translation at the start of the current action.
+    pub start: Vec3,
+    /// Translation after the previous fixed step.
+    pub previous: Vec3,
+    /// Translation after the latest fixed step.
+    pub current: Vec3,
+}
+
 /// Describe entities action duration
 #[derive(Component)]
 pub struct ActionDurationPHF(pub Map<&'static str, f32>);
 
+/// Describe the duration factor of moving to a tile, by tile kind
+pub struct TerrainCostPHF(pub Map<&'static str, f32>);
+
 impl ActionDurationPHF {
     pub fn lookup(&self, action: &Action) -> f32 {
         *self
@@ -130,7 +182,56 @@ impl ActionDurationPHF {
             .unwrap_or_else(|| panic!("Unable to lookup {:?} in ActionDurationPHF!", action))
     }
 
-    pub fn generate_timer(&self, action: &Action) -> ActionTimer {
-        ActionTimer(Timer::from_seconds(self.lookup(action), TimerMode::Once))
+    /// Delay between the start of an attack and its hit, stored as `AttackHit`.
+    pub fn hit_delay(&self) -> f32 {
+        *self
+            .0
+            .get("AttackHit")
+            .expect("Unable to lookup AttackHit in ActionDurationPHF!")
+    }
+
+    /// Creates the timer of an action, diagonal moves being longer.
+    ///
+    /// `terrain_cost` is the duration factor of the destination tile, see `TerrainCostPHF`.
+    pub fn generate_timer(&self, action: &Action, terrain_cost: f32) -> ActionTimer {
+        let factor = if action.kind.is_moving() && action.direction.is_diagonal() {
+            DIAGONAL_DURATION_FACTOR
+        } else {
+            1.
+        };
+        ActionTimer(Timer::from_seconds(
+            self.lookup(action) * factor * terrain_cost,
+            TimerMode::Once,
+        ))
+    }
+}
+
+impl Motion {
+    /// Translation the current action started from.
+    ///
+    /// Before its first fixed step, the action starts from the current translation.
+    pub fn start_translation(&self, transform: &Transform, timer: &ActionTimer) -> Vec3 {
+        if timer.elapsed().is_zero() {
+            transform.translation
+        } else {
+            self.start
+        }
+    }
+}
+
+impl ActionKind {
+    /// Checks if the action moves the entity.
+    pub const fn is_moving(&self) -> bool {
+        matches!(
+            self,
+            ActionKind::Walk | ActionKind::Run | ActionKind::Swim | ActionKind::Knockback
+        )
+    }
+}
+
+impl TerrainCostPHF {
+    /// Duration factor of moving to a tile, tiles without cost don't slow down.
+    pub fn lookup(&self, kind: TileKind) -> f32 {
+        self.0.get(&kind.to_string()).copied().unwrap_or(1.)
     }
 }
@@ -2,7 +2,7 @@ use benimator::*;
 use bevy::prelude::*;
 use bevy::utils::HashMap;
 
-use super::action::Action;
+use super::action::{Action, ActionDirection, ActionKind};
 
 #[derive(Default, Component, Deref, DerefMut)]
 pub struct AnimationState(pub benimator::State);
@@ -40,13 +40,23 @@ impl TileAnimationMap {
 }
 
 impl ActionAnimationMap {
+    /// Looks up the animation of an action.
+    ///
+    /// Missing animations fall back to the horizontal direction (for diagonals),
+    /// then to the same kind facing down, then to standing.
     pub fn lookup(&self, action: &Action) -> &Animation {
+        let horizontal = action.direction.horizontal();
         self.get(action)
+            .or_else(|| self.get(&Action::new(action.kind.clone(), horizontal.clone())))
+            .or_else(|| self.get(&Action::new(action.kind.clone(), ActionDirection::Down)))
+            .or_else(|| self.get(&Action::new(ActionKind::Stand, horizontal)))
+            .or_else(|| self.get(&Action::new(ActionKind::Stand, ActionDirection::Down)))
             .unwrap_or_else(|| panic!("Unable to lookup {:?} in ActionAnimationMap!", action))
     }
 }
 
 /// Represents a grid of sprites for animation purposes.
+#[derive(Clone)]
 pub struct AnimationSpriteGrid {
     pub size: Vec2,
     pub columns: usize,
@@ -0,0 +1,190 @@
+use bevy::prelude::*;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use std::time::Duration;
+
+use crate::constants::bot::*;
+
+use super::action::{Action, ActionDirection, ActionKind};
+use super::command::Command;
+
+/// What a bot knows about its surroundings when thinking.
+pub struct BotPerception {
+    /// Position of the closest user in view, relative to the bot.
+    pub user: Option<IVec2>,
+    /// Directions the bot can walk to without ending in the water.
+    pub safe_directions: Vec<ActionDirection>,
+    /// Position of the closest hostile creature near the bot or the user, relative to the bot.
+    pub threat: Option<IVec2>,
+}
+
+/// Decides what a bot does next, without any network.
+pub trait BotBrain: Send + Sync {
+    /// Returns the next commands, an empty vector meaning doing nothing.
+    fn think(&mut self, perception: &BotPerception) -> Vec<Command>;
+}
+
+/// Node of a behavior tree.
+pub enum Behavior {
+    /// Runs the children in order until one of them succeeds.
+    Selector(Vec<Behavior>),
+    /// Runs the child only if the condition holds.
+    Condition(fn(&BotPerception) -> bool, Box<Behavior>),
+    /// Produces commands, `None` being a failure.
+    Task(fn(&BotPerception, &mut StdRng) -> Option<Vec<Command>>),
+}
+
+/// Deterministic brain running a behavior tree.
+///
+/// Defends against hostile creatures, follows the player, otherwise wanders
+/// around or emotes, always avoiding water.
+pub struct BehaviorTreeBrain {
+    root: Behavior,
+    rng: StdRng,
+}
+
+/// Component running an offline brain when no model is available.
+#[derive(Component)]
+pub struct OfflineBrain {
+    brain: Box<dyn BotBrain>,
+    timer: Timer,
+}
+
+impl Behavior {
+    /// Runs the node, returns `None` if it failed.
+    pub fn run(&self, perception: &BotPerception, rng: &mut StdRng) -> Option<Vec<Command>> {
+        match self {
+            Behavior::Selector(children) => {
+                children.iter().find_map(|child| child.run(perception, rng))
+            }
+            Behavior::Condition(condition, child) => {
+                if condition(perception) {
+                    child.run(perception, rng)
+                } else {
+                    None
+                }
+            }
+            Behavior::Task(task) => task(perception, rng),
+        }
+    }
+}
+
+impl BehaviorTreeBrain {
+    /// Creates the default behavior tree, seeded for reproducible decisions.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            root: Behavior::Selector(vec![
+                Behavior::Task(defend),
+                Behavior::Condition(is_user_far, Box::new(Behavior::Task(follow_user))),
+                Behavior::Task(wander),
+                Behavior::Task(idle),
+            ]),
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl BotBrain for BehaviorTreeBrain {
+    fn think(&mut self, perception: &BotPerception) -> Vec<Command> {
+        self.root.run(perception, &mut self.rng).unwrap_or_default()
+    }
+}
+
+impl OfflineBrain {
+    pub fn new(brain: impl BotBrain + 'static) -> Self {
+        Self {
+            brain: Box::new(brain),
+            timer: Timer::from_seconds(BRAIN_THINK_INTERVAL, TimerMode::Repeating),
+        }
+    }
+
+    /// Ticks the brain, returns `true` when it is time to think.
+    pub fn tick(&mut self, delta: Duration) -> bool {
+        self.timer.tick(delta).just_finished()
+    }
+
+    /// Decides the next commands.
+    pub fn think(&mut self, perception: &BotPerception) -> Vec<Command> {
+        self.brain.think(perception)
+    }
+}
+
+/// Checks if the user is too far away.
+fn is_user_far(perception: &BotPerception) -> bool {
+    perception
+        .user
+        .is_some_and(|user| user.abs().max_element() > BRAIN_FOLLOW_DISTANCE)
+}
+
+/// Attacks the closest threat once adjacent, walking toward it otherwise.
+fn defend(perception: &BotPerception, _rng: &mut StdRng) -> Option<Vec<Command>> {
+    let threat = perception.threat?;
+    if threat.abs().max_element() <= 1 {
+        let direction = ActionDirection::from_vector(threat)?;
+        return Some(vec![Command::Act(Action::new(
+            ActionKind::Attack,
+            direction,
+        ))]);
+    }
+    // One step at a time, the threat moves
+    walk_toward(threat, perception).map(|commands| commands.into_iter().take(1).collect())
+}
+
+/// Walks toward the user.
+fn follow_user(perception: &BotPerception, _rng: &mut StdRng) -> Option<Vec<Command>> {
+    walk_toward(perception.user?, perception)
+}
+
+/// Walks toward a relative position, along the longest axis first.
+fn walk_toward(target: IVec2, perception: &BotPerception) -> Option<Vec<Command>> {
+    let horizontal = if target.x > 0 {
+        ActionDirection::Right
+    } else {
+        ActionDirection::Left
+    };
+    let vertical = if target.y > 0 {
+        ActionDirection::Up
+    } else {
+        ActionDirection::Down
+    };
+    let axes = if target.x.abs() >= target.y.abs() {
+        [(horizontal, target.x.abs()), (vertical, target.y.abs())]
+    } else {
+        [(vertical, target.y.abs()), (horizontal, target.x.abs())]
+    };
+
+    axes.into_iter()
+        .find(|(direction, distance)| {
+            *distance > 0 && perception.safe_directions.contains(direction)
+        })
+        .map(|(direction, distance)| {
+            let steps = (distance as usize).min(BRAIN_MAX_STEPS);
+            vec![Command::Act(Action::new(ActionKind::Walk, direction)); steps]
+        })
+}
+
+/// Walks a few steps in a random safe direction, from time to time.
+fn wander(perception: &BotPerception, rng: &mut StdRng) -> Option<Vec<Command>> {
+    if !rng.gen_bool(BRAIN_WANDER_CHANCE) {
+        return None;
+    }
+    let direction = perception.safe_directions.choose(rng)?.clone();
+    let steps = rng.gen_range(1..=BRAIN_MAX_STEPS);
+    Some(vec![
+        Command::Act(Action::new(ActionKind::Walk, direction));
+        steps
+    ])
+}
+
+/// Stays in place, emoting from time to time.
+fn idle(_perception: &BotPerception, rng: &mut StdRng) -> Option<Vec<Command>> {
+    if rng.gen_bool(BRAIN_EMOTE_CHANCE) {
+        Some(vec![Command::Act(Action::new(
+            ActionKind::Type,
+            ActionDirection::Down,
+        ))])
+    } else {
+        Some(Vec::new())
+    }
+}
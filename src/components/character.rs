@@ -1,3 +1,4 @@
+use std::fmt;
 use std::sync::atomic::AtomicBool;
 
 use bevy::prelude::*;
@@ -15,3 +16,78 @@ pub struct IsUser;
 /// Component indicating that an entity is a bot.
 #[derive(Component)]
 pub struct IsBot;
+
+/// Component remembering who blocked the last move of a character.
+#[derive(Component, Deref)]
+pub struct BlockedBy(pub Entity);
+
+/// Component selecting how a character moves.
+#[derive(Component, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MovementMode {
+    /// Moves one tile per action, waiting for the action to finish.
+    #[default]
+    TileStep,
+    /// Moves continuously with a velocity, colliding with relief boundaries.
+    Free,
+}
+
+/// Component holding the velocity of a freely moving character.
+#[derive(Component, Default, Deref, DerefMut)]
+pub struct Velocity(pub Vec2); // unit: pixels per second
+
+/// Component holding the health a character is restored to.
+#[derive(Component, Deref, Clone, Copy)]
+pub struct MaxHealth(pub u8);
+
+/// Component holding where a character respawns after dying.
+#[derive(Component, Deref, Clone, Copy)]
+pub struct SpawnPoint(pub Vec2);
+
+/// Component remembering the tile a character last stood on, to detect falls.
+#[derive(Component, Default, Deref, DerefMut)]
+pub struct Footing(pub Option<IVec2>);
+
+/// Component counting the time a character spent in water without a break.
+#[derive(Component, Default, Deref, DerefMut)]
+pub struct Submersion(pub f32); // unit: seconds
+
+/// Component preventing damage until the timer is finished.
+#[derive(Component, Deref, DerefMut)]
+pub struct Invulnerable(pub Timer);
+
+/// Component indicating that a character is dead and waiting to respawn.
+#[derive(Component)]
+pub struct Dead;
+
+/// Represents why the health of a character changed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HealthChangeCause {
+    /// Fell from a ledge.
+    Fall,
+    /// Stayed too long in water.
+    Drowning,
+    /// Used a healing item, by id.
+    Item(&'static str),
+    /// Hit by another character or creature.
+    Attack(Entity),
+}
+
+/// Event sent to damage or heal a character.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct HealthChangeEvent {
+    pub entity: Entity,
+    /// Health gained, negative for damage.
+    pub amount: i32,
+    pub cause: HealthChangeCause,
+}
+
+impl fmt::Display for HealthChangeCause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HealthChangeCause::Fall => write!(f, "a fall"),
+            HealthChangeCause::Drowning => write!(f, "drowning"),
+            HealthChangeCause::Item(id) => write!(f, "{}", id),
+            HealthChangeCause::Attack(_) => write!(f, "an attack"),
+        }
+    }
+}
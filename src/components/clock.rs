@@ -0,0 +1,97 @@
+use std::fmt;
+
+use bevy::prelude::*;
+
+use crate::constants::clock::*;
+
+/// Part of the day, changing the lighting of the world.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeOfDay {
+    Dawn,
+    Day,
+    Dusk,
+    Night,
+}
+
+/// Resource keeping the in-game time.
+#[derive(Resource)]
+pub struct WorldClock {
+    /// Number of days elapsed since the start.
+    pub day: u32,
+    /// Hour of the current day, between 0 and 24.
+    pub hour: f32,
+}
+
+impl Default for WorldClock {
+    fn default() -> Self {
+        Self {
+            day: 0,
+            hour: CLOCK_START_HOUR,
+        }
+    }
+}
+
+impl WorldClock {
+    /// Advances the in-game time by a real duration.
+    pub fn advance(&mut self, delta: f32) {
+        self.hour += delta * 24. / CLOCK_DAY_DURATION;
+        while self.hour >= 24. {
+            self.hour -= 24.;
+            self.day += 1;
+        }
+    }
+
+    /// Gets the part of the current day.
+    pub fn time_of_day(&self) -> TimeOfDay {
+        match self.hour {
+            hour if hour < DAWN_HOUR => TimeOfDay::Night,
+            hour if hour < DAY_HOUR => TimeOfDay::Dawn,
+            hour if hour < DUSK_HOUR => TimeOfDay::Day,
+            hour if hour < NIGHT_HOUR => TimeOfDay::Dusk,
+            _ => TimeOfDay::Night,
+        }
+    }
+
+    /// Gets the color laid over the world, blending the surrounding keyframes.
+    pub fn tint(&self) -> Color {
+        let next = DAYLIGHT_TINTS
+            .iter()
+            .position(|(hour, _)| *hour > self.hour)
+            .unwrap_or(DAYLIGHT_TINTS.len() - 1)
+            .max(1);
+        let (start_hour, start) = DAYLIGHT_TINTS[next - 1];
+        let (end_hour, end) = DAYLIGHT_TINTS[next];
+        let ratio = ((self.hour - start_hour) / (end_hour - start_hour)).clamp(0., 1.);
+        Color::rgba(
+            start.r() + (end.r() - start.r()) * ratio,
+            start.g() + (end.g() - start.g()) * ratio,
+            start.b() + (end.b() - start.b()) * ratio,
+            start.a() + (end.a() - start.a()) * ratio,
+        )
+    }
+}
+
+impl fmt::Display for TimeOfDay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeOfDay::Dawn => write!(f, "dawn"),
+            TimeOfDay::Day => write!(f, "day"),
+            TimeOfDay::Dusk => write!(f, "dusk"),
+            TimeOfDay::Night => write!(f, "night"),
+        }
+    }
+}
+
+impl fmt::Display for WorldClock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let minutes = (self.hour * 60.) as u32;
+        write!(
+            f,
+            "{} ({:02}:{:02}, day {})",
+            self.time_of_day(),
+            minutes / 60,
+            minutes % 60,
+            self.day + 1
+        )
+    }
+}
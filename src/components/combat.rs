@@ -0,0 +1,41 @@
+use bevy::prelude::*;
+
+use super::action::{Action, ActionDirection};
+
+/// Component representing an attack about to hit a tile.
+///
+/// Whoever occupies the tile when the timer finishes is hit.
+#[derive(Component)]
+pub struct Hitbox {
+    /// The attacking character, never hit by its own attack.
+    pub owner: Entity,
+    pub tile_pos: IVec2,
+    /// Direction of the attack, the hit character is pushed that way.
+    pub direction: ActionDirection,
+    pub damage: u8,
+    pub timer: Timer,
+}
+
+/// Component pushing a character one tile away once its current step is over.
+#[derive(Component, Deref)]
+pub struct Knockback(pub ActionDirection);
+
+impl Hitbox {
+    /// Creates the hitbox of an attack on the tile faced by the attacker.
+    ///
+    /// # Parameters
+    /// - `owner`: The attacking character.
+    /// - `action`: The attack action.
+    /// - `position`: The tile position of the attacker.
+    /// - `damage`: The health lost by the hit character.
+    /// - `delay`: Time before the attack hits, see `ActionDurationPHF::hit_delay`.
+    pub fn new(owner: Entity, action: &Action, position: &IVec2, damage: u8, delay: f32) -> Self {
+        Self {
+            owner,
+            tile_pos: *position + action.direction.to_vector(),
+            direction: action.direction.clone(),
+            damage,
+            timer: Timer::from_seconds(delay, TimerMode::Once),
+        }
+    }
+}
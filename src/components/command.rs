@@ -0,0 +1,157 @@
+use bevy::log;
+use bevy::prelude::*;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use crate::util::parser::{self, ParseError};
+
+use super::action::Action;
+use super::item::ItemAction;
+use super::quest::QuestProposal;
+
+/// Represents a command issued to a bot.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    /// Performs a single action.
+    Act(Action),
+    /// Says something to another character.
+    SayTo { target: String, message: String },
+    /// Keeps following a character.
+    Follow(FollowTarget),
+    /// Stops following or going somewhere.
+    Stop,
+    /// Stands still for a number of seconds.
+    Wait(f32),
+    /// Walks to the tile at the given offset from the bot.
+    GoTo(IVec2),
+    /// Picks up, drops or gives items.
+    Item(ItemAction),
+    /// Keeps attacking a character or creature until it is gone.
+    Attack(AttackTarget),
+    /// Proposes a quest to the user.
+    ProposeQuest(QuestProposal),
+}
+
+/// Component making a bot stay close to a character until cancelled.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct FollowTarget {
+    /// Name of the followed character.
+    pub target: String,
+    /// Distance kept from the target, in tiles.
+    pub distance: i32,
+}
+
+/// Component making a bot chase and attack a character or creature until cancelled.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct AttackTarget {
+    /// Name of the attacked character or creature, the closest one is attacked.
+    pub target: String,
+}
+
+/// Component making a bot walk to a tile position until reached or cancelled.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Deref)]
+pub struct Destination(pub IVec2);
+
+/// Component making a bot wait before searching a path to the same tile again.
+///
+/// Added when no path leads to the tile or its first step is blocked, so
+/// unreachable targets are not searched every frame.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct PathBackoff {
+    /// The tile no path was found to.
+    pub target: IVec2,
+    /// Elapsed time before which the path is not searched again.
+    pub until: f32, // unit: seconds
+}
+
+/// Component holding the commands a character has yet to execute.
+///
+/// Shared with the brains filling it, possibly from another thread.
+/// The user's queue holds the steps toward the clicked tile.
+#[derive(Component, Clone, Default, Deref)]
+pub struct ActionQueue(pub Arc<RwLock<VecDeque<Command>>>);
+
+impl Command {
+    /// Parses a command text, see `parser::parse` for the grammar.
+    ///
+    /// # Arguments
+    /// * `commands` - A command text, usually containing one command per line.
+    ///
+    /// # Returns
+    /// The parsed commands, or the first error with its line and column.
+    pub fn parse(commands: &str) -> Result<Vec<Command>, ParseError> {
+        parser::parse(commands)
+    }
+}
+
+impl ActionQueue {
+    /// Checks if the queue is empty, a locked queue is considered busy.
+    pub fn is_empty(&self) -> bool {
+        self.try_read()
+            .map(|queue| queue.is_empty())
+            .unwrap_or(false)
+    }
+
+    /// Appends commands to the queue, if it is not locked.
+    ///
+    /// # Returns
+    /// Whether the commands were appended, they are dropped with a warning otherwise.
+    pub fn extend(&self, commands: impl IntoIterator<Item = Command>) -> bool {
+        match self.try_write() {
+            Ok(mut queue) => {
+                queue.extend(commands);
+                true
+            }
+            Err(_) => {
+                log::warn!(
+                    "Action queue locked, dropping {} commands",
+                    commands.into_iter().count()
+                );
+                false
+            }
+        }
+    }
+
+    /// Replaces every command by new ones, if the queue is not locked.
+    ///
+    /// # Returns
+    /// Whether the commands were replaced, the queue is left as is otherwise.
+    pub fn replace(&self, commands: impl IntoIterator<Item = Command>) -> bool {
+        match self.try_write() {
+            Ok(mut queue) => {
+                queue.clear();
+                queue.extend(commands);
+                true
+            }
+            Err(_) => {
+                log::warn!("Action queue locked, not replacing its commands");
+                false
+            }
+        }
+    }
+
+    /// Removes every command, if the queue is not locked.
+    ///
+    /// # Returns
+    /// Whether the queue was cleared, it is left as is with a warning otherwise.
+    pub fn clear(&self) -> bool {
+        match self.try_write() {
+            Ok(mut queue) => {
+                queue.clear();
+                true
+            }
+            Err(_) => {
+                log::warn!("Action queue locked, not clearing it");
+                false
+            }
+        }
+    }
+
+    /// Pops the next command, if the queue is not locked.
+    pub fn pop_front(&self) -> Option<Command> {
+        self.try_write()
+            .ok()
+            .and_then(|mut queue| queue.pop_front())
+    }
+}
@@ -1,5 +1,33 @@
-use bevy::ecs::component::Component;
+use bevy::prelude::*;
 
 /// Component representing the game camera.
 #[derive(Component)]
 pub struct IsGameCamera;
+
+/// Component despawning its entity once the timer is finished.
+#[derive(Component, Deref, DerefMut)]
+pub struct Lifetime(pub Timer);
+
+/// Component indicating that an entity is a speech bubble.
+#[derive(Component)]
+pub struct SpeechBubble;
+
+/// Component indicating that an entity highlights the tile under the cursor.
+#[derive(Component)]
+pub struct TileCursor;
+
+/// Component indicating that a UI node tints the world with the daylight.
+#[derive(Component)]
+pub struct DaylightOverlay;
+
+/// Component indicating that a UI node holds the quest log, shown or hidden as a whole.
+#[derive(Component)]
+pub struct QuestLogPanel;
+
+/// Component indicating that a UI text lists the quests of the user.
+#[derive(Component)]
+pub struct QuestLogText;
+
+/// Component indicating that a UI node is the filling of the user's health bar.
+#[derive(Component)]
+pub struct HealthBar;
@@ -0,0 +1,147 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::constants::exploration::EXPLORED_SAVE_INTERVAL;
+use crate::constants::map::{CHUNK_SIZE, CHUNK_TILES};
+use crate::util::position::{relative_tile_pos, tile_pos_to_chunk_pos};
+
+/// Bitmap of the explored tiles of a chunk, one bit per tile.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deref, Serialize, Deserialize)]
+pub struct Explored(pub u64);
+
+// Every tile of a chunk needs its bit
+const _: () = assert!(CHUNK_TILES <= u64::BITS as usize);
+
+/// What is remembered of a generated chunk.
+#[derive(Debug, Clone)]
+pub struct ExploredChunk {
+    /// Relief level of each tile, row by row.
+    pub levels: [u8; CHUNK_TILES],
+    pub explored: Explored,
+}
+
+/// Resource remembering the chunks ever generated, and their explored tiles.
+///
+/// Chunks stay remembered after being despawned, the explored tiles are saved.
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct ExploredMap(pub HashMap<IVec2, ExploredChunk>);
+
+/// Explored tiles written in the save file.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ExploredSave {
+    pub chunks: Vec<(IVec2, Explored)>,
+}
+
+/// Resource timing the saves of the explored tiles.
+#[derive(Resource, Deref, DerefMut)]
+pub struct ExploredAutosave(pub Timer);
+
+impl Explored {
+    /// Index of the bit of a tile.
+    fn bit(tile_pos: &IVec2) -> u64 {
+        let position = relative_tile_pos(tile_pos);
+        1 << (position.y * CHUNK_SIZE.x + position.x)
+    }
+
+    /// Checks if a tile of the chunk is explored.
+    pub fn contains(&self, tile_pos: &IVec2) -> bool {
+        self.0 & Self::bit(tile_pos) != 0
+    }
+
+    /// Marks a tile of the chunk as explored.
+    ///
+    /// # Returns
+    /// `true` if the tile was not explored yet.
+    pub fn insert(&mut self, tile_pos: &IVec2) -> bool {
+        let is_new = !self.contains(tile_pos);
+        self.0 |= Self::bit(tile_pos);
+        is_new
+    }
+}
+
+impl ExploredChunk {
+    /// Retrieves the relief level of a tile of the chunk.
+    pub fn level(&self, tile_pos: &IVec2) -> u8 {
+        let position = relative_tile_pos(tile_pos);
+        self.levels[(position.y * CHUNK_SIZE.x + position.x) as usize]
+    }
+}
+
+impl ExploredMap {
+    /// Retrieves the relief level of an explored tile.
+    ///
+    /// # Parameters
+    /// - `tile_pos`: The tile position.
+    ///
+    /// # Returns
+    /// The relief level, or `None` if the tile is unexplored.
+    pub fn level(&self, tile_pos: &IVec2) -> Option<u8> {
+        self.get(&tile_pos_to_chunk_pos(tile_pos))
+            .filter(|chunk| chunk.explored.contains(tile_pos))
+            .map(|chunk| chunk.level(tile_pos))
+    }
+
+    /// Checks if a tile is explored.
+    pub fn is_explored(&self, tile_pos: &IVec2) -> bool {
+        self.get(&tile_pos_to_chunk_pos(tile_pos))
+            .is_some_and(|chunk| chunk.explored.contains(tile_pos))
+    }
+
+    /// Finds the direction where most of the tiles around are unexplored.
+    ///
+    /// # Parameters
+    /// - `from`: The tile in the middle.
+    /// - `distance`: The distance looked at, in tiles.
+    /// - `step`: The distance between two looked at tiles.
+    ///
+    /// # Returns
+    /// The unit vector of the direction, or `None` if everything is explored.
+    pub fn unexplored_direction(&self, from: IVec2, distance: i32, step: usize) -> Option<IVec2> {
+        let mut counts = [
+            (IVec2::X, 0),
+            (IVec2::NEG_X, 0),
+            (IVec2::Y, 0),
+            (IVec2::NEG_Y, 0),
+        ];
+        for y in (-distance..=distance).step_by(step) {
+            for x in (-distance..=distance).step_by(step) {
+                let offset = IVec2::new(x, y);
+                if offset == IVec2::ZERO || self.is_explored(&(from + offset)) {
+                    continue;
+                }
+                for (direction, count) in counts.iter_mut() {
+                    // Tiles on a diagonal count for both directions
+                    if offset.dot(*direction) >= offset.abs().max_element() {
+                        *count += 1;
+                    }
+                }
+            }
+        }
+        counts
+            .into_iter()
+            .filter(|(_, count)| *count > 0)
+            .max_by_key(|(_, count)| *count)
+            .map(|(direction, _)| direction)
+    }
+
+    /// Gets the explored tiles to save.
+    pub fn to_save(&self) -> ExploredSave {
+        ExploredSave {
+            chunks: self
+                .iter()
+                .filter(|(_, chunk)| *chunk.explored != 0)
+                .map(|(chunk_pos, chunk)| (*chunk_pos, chunk.explored))
+                .collect(),
+        }
+    }
+}
+
+impl Default for ExploredAutosave {
+    fn default() -> Self {
+        Self(Timer::from_seconds(
+            EXPLORED_SAVE_INTERVAL,
+            TimerMode::Repeating,
+        ))
+    }
+}
@@ -2,82 +2,178 @@ use async_compat::Compat;
 use bevy::log;
 use bevy::prelude::*;
 use bevy::tasks::AsyncComputeTaskPool;
-use std::collections::VecDeque;
+use bevy::utils::HashMap;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::sync::Mutex;
+use tokio::sync::OwnedSemaphorePermit;
 use tokio::sync::RwLock;
+use tokio::sync::Semaphore;
 
+use crate::constants::bot::*;
 use crate::util::gpt::*;
+use crate::util::memory::*;
 
-use super::action::Action;
+use super::command::{ActionQueue, Command};
+
+/// Describes a bot spawned at startup.
+pub struct BotDefinition {
+    /// Name of the bot, also used in its prompts.
+    pub name: &'static str,
+    /// Spawn position in pixels.
+    pub spawn: Vec2,
+    /// Personality given to the model.
+    pub persona: &'static str,
+    /// Model used by this bot, `None` to use the default one.
+    pub model: Option<&'static str>,
+    /// Path of the sprite sheet.
+    pub sprite: &'static str,
+}
 
 /// Represents a conversation with the ChatGPT model.
 #[derive(Clone)]
 struct GPTConversation {
     client: ChatGPT,
-    context: Vec<String>,
-    busy: Arc<AtomicBool>,
+    system_prompt: String,
+    memory: ConversationMemory,
+    available: Arc<AtomicBool>,
 }
 
 /// Component representing a GPT-based agent.
 #[derive(Component)]
 pub struct GPTAgent {
     conversation: Arc<RwLock<GPTConversation>>,
-    pub action_queue: Arc<RwLock<VecDeque<Action>>>,
+    action_queue: ActionQueue,
+    inbox: Arc<Mutex<Vec<String>>>,
+    busy: Arc<AtomicBool>,
+    available: Arc<AtomicBool>,
+    /// Elapsed time before retrying an unavailable model.
+    pub retry_at: f32,
+}
+
+/// Event sent when a bot says something to another character.
+#[derive(Event)]
+pub struct BotSpeechEvent {
+    pub speaker: Entity,
+    pub target: String,
+    pub message: String,
 }
 
+/// Resource keeping track of the dialogues between bots.
+#[derive(Resource, Default)]
+pub struct BotDialogues(
+    /// Number of messages exchanged and time of the last one, by pair of names.
+    HashMap<(String, String), (u32, f32)>,
+);
+
+/// Resource limiting the number of GPT requests in flight.
+#[derive(Resource, Deref)]
+pub struct GPTRequestLimiter(Arc<Semaphore>);
+
 impl GPTConversation {
     /// Creates a new `GPTConversation` with the provided ChatGPT client.
-    fn new(client: ChatGPT) -> Self {
+    fn new(client: ChatGPT, system_prompt: String, available: Arc<AtomicBool>) -> Self {
         Self {
             client,
-            context: Vec::new(),
-            busy: Arc::new(AtomicBool::new(false)),
+            system_prompt,
+            memory: ConversationMemory::default(),
+            available,
         }
     }
 
-    /// Sends a message to ChatGPT and retrieves the corresponding actions.
-    async fn send_message_get_actions(&self, message: &str) -> Option<Vec<Action>> {
-        if self.busy.swap(true, Ordering::Acquire) {
-            return None;
-        }
+    /// Sends the conversation to ChatGPT and retrieves the corresponding commands.
+    async fn send_messages_get_commands(&mut self, context: &str) -> Option<Vec<Command>> {
+        let mut system = self.system_prompt.clone();
+        system.push('\n');
+        system.push_str(&self.memory.system_section());
+        let mut message = self.memory.history();
+        message.push_str(context);
 
-        log::debug!("Sending:\n{}", message);
-        let actions = match self.client.send_message(message, Role::System).await {
+        log::debug!(
+            "Sending (~{} tokens):\n{}",
+            estimate_tokens(&system) + estimate_tokens(&message),
+            message
+        );
+        let messages = vec![
+            ChatMessage::new(Role::System, system),
+            ChatMessage::new(Role::User, message),
+        ];
+        match self.client.send_messages(&messages).await {
             Ok(response) => {
+                self.available.store(true, Ordering::Release);
                 let response_txt = &response.message().content;
                 log::debug!("Received:\n{}", response_txt);
-                Action::from_command_string(response_txt)
+                self.memory
+                    .push_turn(format!("You answered:\n{}", response_txt));
+                match Command::parse(response_txt) {
+                    Ok(commands) => Some(commands),
+                    Err(e) => {
+                        log::debug!("Cannot parse GPT answer: {}", e);
+                        self.memory.push_turn(format!(
+                            "Your answer was ignored, error at {}. Fix it and answer again.",
+                            e
+                        ));
+                        None
+                    }
+                }
             }
             Err(e) => {
                 log::warn!("Cannot get GPT answer: {}", e);
+                self.available.store(false, Ordering::Release);
                 None
             }
-        };
-
-        self.busy.store(false, Ordering::Release);
+        }
+    }
 
-        actions
+    /// Retrieves commands with extra context.
+    async fn get_commands_with_extra_context(&mut self, context: &str) -> Option<Vec<Command>> {
+        if self.memory.needs_summary() {
+            self.summarize().await;
+        }
+        self.send_messages_get_commands(context).await
     }
 
-    /// Retrieves actions with extra context.
-    async fn get_actions_with_extra_context(&self, context: &str) -> Option<Vec<Action>> {
-        let mut message = self.context.join("\n");
-        message.push_str(context);
-        self.send_message_get_actions(&message).await
+    /// Folds the oldest turns of the conversation into a summary and facts.
+    async fn summarize(&mut self) {
+        let (request, count) = self.memory.summary_request();
+        log::debug!("Summarizing {} turns:\n{}", count, request);
+        let messages = vec![
+            ChatMessage::new(Role::System, MEMORY_SUMMARY_PROMPT),
+            ChatMessage::new(Role::User, request),
+        ];
+        match self.client.send_messages(&messages).await {
+            Ok(response) => {
+                let response_txt = &response.message().content;
+                log::debug!("Received summary:\n{}", response_txt);
+                self.memory.apply_summary(response_txt, count);
+            }
+            Err(e) => {
+                // Still forget, the prompt must not grow unboundedly
+                log::warn!("Cannot summarize conversation: {}", e);
+                self.memory.forget(count);
+            }
+        }
     }
 
     /// Adds context to the conversation's history.
     fn add_context(&mut self, message: String) {
         log::debug!("Adding to context:\n\"{}\"", message);
-        self.context.push(message);
+        self.memory.push_turn(message);
     }
 }
 
 impl GPTAgent {
-    /// Creates a new GPTAgent with the provided API key.
-    pub fn new(key: String, model: String, url: String) -> Option<Self> {
+    /// Creates a new GPTAgent with the provided API key and system prompt.
+    ///
+    /// The commands received from the model are pushed into `action_queue`.
+    pub fn new(
+        key: String,
+        model: String,
+        url: String,
+        system_prompt: String,
+        action_queue: ActionQueue,
+    ) -> Option<Self> {
         let config = ModelConfiguration {
             engine: model,
             api_url: url,
@@ -87,10 +183,21 @@ impl GPTAgent {
         let result = ChatGPT::new(key, config);
 
         match result {
-            Ok(client) => Some(Self {
-                conversation: Arc::new(RwLock::new(GPTConversation::new(client))),
-                action_queue: Arc::new(RwLock::new(VecDeque::new())),
-            }),
+            Ok(client) => {
+                let available = Arc::new(AtomicBool::new(true));
+                Some(Self {
+                    conversation: Arc::new(RwLock::new(GPTConversation::new(
+                        client,
+                        system_prompt,
+                        available.clone(),
+                    ))),
+                    action_queue,
+                    inbox: Arc::new(Mutex::new(Vec::new())),
+                    busy: Arc::new(AtomicBool::new(false)),
+                    available,
+                    retry_at: 0.,
+                })
+            }
             Err(e) => {
                 log::warn!("Cannot create ChatGPT client: {}", e);
                 None
@@ -98,40 +205,96 @@ impl GPTAgent {
         }
     }
 
-    /// Creates actions with extra context from a message.
-    pub fn create_actions_with_extra_context(&self, message: &str) {
+    /// Creates commands with extra context from a message.
+    ///
+    /// The permit is held until the request completes.
+    pub fn create_commands_with_extra_context(&self, message: &str, permit: OwnedSemaphorePermit) {
+        if self.busy.swap(true, Ordering::Acquire) {
+            return;
+        }
+
         let queue_arc = self.action_queue.clone();
         let conversation_arc = self.conversation.clone();
+        let inbox_arc = self.inbox.clone();
+        let busy_arc = self.busy.clone();
         let message = "\n".to_string() + message;
         let thread_pool = AsyncComputeTaskPool::get();
 
         thread_pool
             .spawn(Compat::new(async move {
-                if let Ok(mut queue) = queue_arc.try_write() {
-                    if let Ok(conversation) = conversation_arc.try_read() {
-                        if let Some(actions) =
-                            conversation.get_actions_with_extra_context(&message).await
-                        {
-                            queue.extend(actions);
-                        }
-                    }
+                let mut conversation = conversation_arc.write().await;
+                let pending = inbox_arc
+                    .lock()
+                    .map(|mut inbox| std::mem::take(&mut *inbox))
+                    .unwrap_or_default();
+                for context in pending {
+                    conversation.add_context(context);
+                }
+                let commands = conversation.get_commands_with_extra_context(&message).await;
+                drop(conversation);
+                if let Some(commands) = commands {
+                    queue_arc.0.write().await.extend(commands);
                 }
+                busy_arc.store(false, Ordering::Release);
+                drop(permit);
             }))
             .detach(); // Detach & forget.
     }
 
     /// Adds context to the conversation.
-    pub fn add_context(&mut self, message: &str) {
-        if let Ok(mut conversation) = self.conversation.try_write() {
-            conversation.add_context(message.to_string());
+    ///
+    /// The context is kept aside and merged before the next request,
+    /// so nothing is lost while a request is in flight.
+    pub fn add_context(&self, message: &str) {
+        if let Ok(mut inbox) = self.inbox.lock() {
+            inbox.push(message.to_string());
         }
     }
 
     /// Checks if the GPT agent is busy.
     pub fn is_busy(&self) -> bool {
-        if let Ok(conversation) = self.conversation.try_read() {
-            return conversation.busy.load(Ordering::Relaxed);
+        self.busy.load(Ordering::Relaxed)
+    }
+
+    /// Checks if the last request to the model succeeded.
+    pub fn is_available(&self) -> bool {
+        self.available.load(Ordering::Relaxed)
+    }
+}
+
+impl GPTRequestLimiter {
+    /// Creates a new limiter allowing `permits` requests at the same time.
+    pub fn new(permits: usize) -> Self {
+        Self(Arc::new(Semaphore::new(permits)))
+    }
+
+    /// Tries to reserve a request slot, returns `None` if all slots are taken.
+    pub fn try_acquire(&self) -> Option<OwnedSemaphorePermit> {
+        self.0.clone().try_acquire_owned().ok()
+    }
+}
+
+impl BotDialogues {
+    /// Registers a message between two bots.
+    ///
+    /// Returns `false` if the dialogue is too long and the message must be dropped.
+    pub fn register(&mut self, speaker: &str, listener: &str, now: f32) -> bool {
+        let key = if speaker < listener {
+            (speaker.to_string(), listener.to_string())
+        } else {
+            (listener.to_string(), speaker.to_string())
+        };
+        let (turns, last) = self.0.entry(key).or_insert((0, now));
+
+        if now - *last > BOT_DIALOGUE_COOLDOWN {
+            *turns = 0;
         }
-        false
+        if *turns >= BOT_MAX_DIALOGUE_TURNS {
+            return false;
+        }
+
+        *turns += 1;
+        *last = now;
+        true
     }
 }
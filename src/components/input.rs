@@ -0,0 +1,115 @@
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+use serde::Deserialize;
+
+use crate::components::action::ActionDirection;
+
+/// Abstract actions the user can trigger, whatever the device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub enum InputAction {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    Run,
+    Talk,
+    Interact,
+    PickUp,
+    Drop,
+    Give,
+    UseItem,
+    Attack,
+    Block,
+    ToggleMovement,
+    QuestLog,
+    WorldMap,
+}
+
+/// Keys and gamepad buttons triggering an input action.
+#[derive(Debug, Clone, Deserialize)]
+pub struct InputBinding {
+    pub action: InputAction,
+    #[serde(default)]
+    pub keys: Vec<KeyCode>,
+    #[serde(default)]
+    pub buttons: Vec<GamepadButtonType>,
+}
+
+/// Resource mapping the devices to input actions, loaded from the input config file.
+#[derive(Resource, Debug, Clone, Deserialize)]
+pub struct InputBindings {
+    pub bindings: Vec<InputBinding>,
+    /// Gamepad axis moving horizontally.
+    pub move_axis_x: GamepadAxisType,
+    /// Gamepad axis moving vertically.
+    pub move_axis_y: GamepadAxisType,
+    /// Stick deflection ignored, from 0 to 1.
+    pub deadzone: f32,
+}
+
+/// Resource holding the input actions of the current frame.
+#[derive(Resource, Default)]
+pub struct ActionInput {
+    /// Requested movement, its length is at most 1.
+    pub movement: Vec2,
+    pressed: HashSet<InputAction>,
+    just_pressed: HashSet<InputAction>,
+}
+
+impl InputBindings {
+    /// Parses input bindings.
+    ///
+    /// # Parameters
+    /// - `config`: The content of an input config file, in JSON.
+    ///
+    /// # Returns
+    /// The bindings, or the parsing error.
+    pub fn from_config(config: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(config)
+    }
+}
+
+impl ActionInput {
+    /// Clears the actions of the previous frame.
+    pub fn clear(&mut self) {
+        self.movement = Vec2::ZERO;
+        self.pressed.clear();
+        self.just_pressed.clear();
+    }
+
+    /// Records an action as held, and as new if it was just pressed.
+    pub fn press(&mut self, action: InputAction, just_pressed: bool) {
+        self.pressed.insert(action);
+        if just_pressed {
+            self.just_pressed.insert(action);
+        }
+    }
+
+    /// Checks if an action is held.
+    pub fn pressed(&self, action: InputAction) -> bool {
+        self.pressed.contains(&action)
+    }
+
+    /// Checks if an action started this frame.
+    pub fn just_pressed(&self, action: InputAction) -> bool {
+        self.just_pressed.contains(&action)
+    }
+
+    /// Snaps the requested movement to the nearest of the eight directions.
+    ///
+    /// # Returns
+    /// The direction, or `None` if no movement is requested.
+    pub fn direction(&self) -> Option<ActionDirection> {
+        let movement = self.movement.normalize_or_zero();
+        // A component counts when the movement is within 22.5° of its axis or diagonals
+        let threshold = std::f32::consts::FRAC_PI_8.sin();
+        let axis = |value: f32| {
+            if value.abs() > threshold {
+                value.signum() as i32
+            } else {
+                0
+            }
+        };
+        ActionDirection::from_vector(IVec2::new(axis(movement.x), axis(movement.y)))
+    }
+}
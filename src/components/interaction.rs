@@ -0,0 +1,21 @@
+use bevy::prelude::*;
+
+/// Component making an entity react to the `Interact` action of a facing character.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub enum Interactable {
+    /// Shows its text.
+    Sign(String),
+    /// Opens once.
+    Chest { is_open: bool },
+    /// Talks with the interacting character.
+    Talk,
+}
+
+/// Event sent when a character interacts with the entity it faces.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct InteractionEvent {
+    /// The interacting character.
+    pub interactor: Entity,
+    /// The interactable entity.
+    pub target: Entity,
+}
@@ -0,0 +1,163 @@
+use bevy::prelude::*;
+use phf::Map;
+
+use crate::constants::item::{INVENTORY_SLOTS, ITEM_REGISTRY};
+
+/// Describes a kind of item.
+#[derive(Debug)]
+pub struct ItemDefinition {
+    pub id: &'static str,
+    pub name: &'static str,
+    /// Index in the item sprite sheet.
+    pub sprite_index: usize,
+    /// Maximum number of items in a single inventory slot.
+    pub stack_size: u32,
+    /// Health restored when used, 0 when the item cannot be used.
+    pub healing: u8,
+    pub tags: &'static [&'static str],
+}
+
+/// Registry of the item definitions, by id.
+pub struct ItemRegistryPHF(pub Map<&'static str, ItemDefinition>);
+
+/// Items of the same kind kept together.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ItemStack {
+    /// Id of the item definition.
+    pub id: &'static str,
+    pub count: u32,
+}
+
+/// Component holding the items carried by a character, in at most `INVENTORY_SLOTS` stacks.
+#[derive(Component, Debug, Default, Clone)]
+pub struct Inventory(Vec<ItemStack>);
+
+/// Component representing items lying in the world.
+#[derive(Component, Debug, Clone, Deref, DerefMut)]
+pub struct ItemDrop(pub ItemStack);
+
+/// Represents what a character can do with items.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ItemAction {
+    /// Picks up the items on the tile of the character and on the faced tile.
+    PickUp,
+    /// Drops items on the tile of the character, the first carried item when not given.
+    Drop { item: Option<String>, count: u32 },
+    /// Gives items to a nearby character, the faced character when not given.
+    Give {
+        item: Option<String>,
+        count: u32,
+        target: Option<String>,
+    },
+    /// Uses an item to heal, the first healing item when not given.
+    Use { item: Option<String> },
+}
+
+/// Event sent when a character does something with items.
+#[derive(Event, Debug, Clone)]
+pub struct ItemActionEvent {
+    pub actor: Entity,
+    pub action: ItemAction,
+}
+
+impl ItemRegistryPHF {
+    /// Retrieves an item definition by id.
+    pub fn get(&self, id: &str) -> Option<&ItemDefinition> {
+        self.0.get(id)
+    }
+
+    /// Finds an item definition by id or name, ignoring the case and a plural `s`.
+    ///
+    /// # Parameters
+    /// - `name`: The name of the item, as written by the user or a bot.
+    ///
+    /// # Returns
+    /// The item definition, or `None` if no item has this name.
+    pub fn find(&self, name: &str) -> Option<&ItemDefinition> {
+        let matches = |candidate: &str| {
+            self.0.values().find(|definition| {
+                definition.id.eq_ignore_ascii_case(candidate)
+                    || definition.name.eq_ignore_ascii_case(candidate)
+            })
+        };
+        matches(name).or_else(|| name.strip_suffix('s').and_then(matches))
+    }
+}
+
+impl ItemStack {
+    pub const fn new(id: &'static str, count: u32) -> Self {
+        Self { id, count }
+    }
+
+    /// Describes the stack, like "3 Apple".
+    pub fn describe(&self) -> String {
+        let name = ITEM_REGISTRY.get(self.id).map_or(self.id, |item| item.name);
+        format!("{} {}", self.count, name)
+    }
+}
+
+impl Inventory {
+    /// Adds items, filling the existing stacks first.
+    ///
+    /// # Parameters
+    /// - `id`: The id of the item definition.
+    /// - `count`: The number of items to add.
+    ///
+    /// # Returns
+    /// The number of items that did not fit.
+    pub fn add(&mut self, id: &'static str, mut count: u32) -> u32 {
+        let Some(definition) = ITEM_REGISTRY.get(id) else {
+            return count;
+        };
+
+        for stack in self.0.iter_mut().filter(|stack| stack.id == id) {
+            let added = count.min(definition.stack_size.saturating_sub(stack.count));
+            stack.count += added;
+            count -= added;
+        }
+        while count > 0 && self.0.len() < INVENTORY_SLOTS {
+            let added = count.min(definition.stack_size);
+            self.0.push(ItemStack::new(id, added));
+            count -= added;
+        }
+        count
+    }
+
+    /// Removes items, emptying the last stacks first.
+    ///
+    /// # Parameters
+    /// - `id`: The id of the item definition.
+    /// - `count`: The number of items to remove.
+    ///
+    /// # Returns
+    /// The number of items actually removed.
+    pub fn remove(&mut self, id: &str, count: u32) -> u32 {
+        let mut removed = 0;
+        for stack in self.0.iter_mut().rev().filter(|stack| stack.id == id) {
+            let taken = (count - removed).min(stack.count);
+            stack.count -= taken;
+            removed += taken;
+        }
+        self.0.retain(|stack| stack.count > 0);
+        removed
+    }
+
+    /// Counts the carried items of a kind.
+    pub fn count(&self, id: &str) -> u32 {
+        self.0
+            .iter()
+            .filter(|stack| stack.id == id)
+            .map(|stack| stack.count)
+            .sum()
+    }
+
+    /// The carried stacks, in slot order.
+    pub fn stacks(&self) -> &[ItemStack] {
+        &self.0
+    }
+
+    /// Id of the item in the first slot.
+    pub fn first(&self) -> Option<&'static str> {
+        self.0.first().map(|stack| stack.id)
+    }
+}
@@ -2,15 +2,26 @@ use std::fmt;
 use std::sync::Arc;
 
 use bevy::ecs::system::CommandQueue;
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 use bevy::tasks::Task;
 use bevy::utils::HashMap;
 use bevy_ecs_tilemap::map::TilemapTexture;
+use bevy_ecs_tilemap::tiles::TileStorage;
+use pathfinding::prelude::astar;
+use strum::IntoEnumIterator;
+use strum_macros::Display;
 use tokio::sync::mpsc;
 use tokio::sync::mpsc::Receiver;
 use tokio::sync::mpsc::Sender;
 
-use crate::constants::map::CHUNK_SPAWNING_CHANNEL_BUFFER_SIZE;
+use crate::components::action::{Action, ActionDirection, ActionKind};
+use crate::constants::action::{SWIM_SHORE_DISTANCE, TERRAIN_COST_MAP};
+use crate::constants::generation::WATER_LEVEL;
+use crate::constants::map::{
+    CHUNK_SPAWNING_CHANNEL_BUFFER_SIZE, PATH_DIAGONAL_COST, PATH_STRAIGHT_COST,
+};
+use crate::util::position::{relative_tile_pos, tile_pos_to_chunk_pos};
 
 /// Resource representing the main tilemap texture.
 #[derive(Resource, Deref, DerefMut)]
@@ -33,6 +44,27 @@ pub struct ReliefLevel(
     pub u32,
 );
 
+/// Kind of a tile, derived from its relief level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+pub enum TileKind {
+    Water,
+    Soil,
+    Grass,
+    DarkGrass,
+}
+
+impl TileKind {
+    /// Finds the kind of the tiles of a relief level.
+    pub fn from_level(level: u32) -> Self {
+        match level {
+            WATER_LEVEL => TileKind::Water,
+            1 => TileKind::Soil,
+            2..=4 => TileKind::Grass,
+            _ => TileKind::DarkGrass,
+        }
+    }
+}
+
 /// Component representing the name used for saving.
 #[derive(Component)]
 pub struct SavingName(
@@ -54,6 +86,30 @@ pub struct LayerId(
     pub u32,
 );
 
+/// Component marking a tile linking two relief levels.
+#[derive(Component, Clone, Deref)]
+pub struct Ramp(
+    /// The direction going down the ramp.
+    pub ActionDirection,
+);
+
+/// Resource tracking the tiles occupied by characters and objects.
+///
+/// A moving character occupies both the tile it leaves and the one it goes to.
+#[derive(Resource, Default, Deref)]
+pub struct Occupancy(
+    /// A HashMap that maps tile positions to their occupant.
+    HashMap<IVec2, Entity>,
+);
+
+/// System parameter giving access to the terrain of the spawned chunks.
+#[derive(SystemParam)]
+pub struct Terrain<'w, 's> {
+    chunk_map: Res<'w, ChunkMap>,
+    chunk_query: Query<'w, 's, &'static TileStorage>,
+    tile_query: Query<'w, 's, (&'static ReliefLevel, Option<&'static Ramp>)>,
+}
+
 /// Component representing a chunk task with a command queue.
 #[derive(Component, Deref)]
 pub struct ChunkTask(pub Task<CommandQueue>);
@@ -95,9 +151,305 @@ impl fmt::Display for ReliefLevel {
     }
 }
 
+impl Occupancy {
+    /// Forgets every occupied tile.
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// Marks a tile as occupied by an entity.
+    pub fn occupy(&mut self, tile_pos: IVec2, entity: Entity) {
+        self.0.insert(tile_pos, entity);
+    }
+
+    /// Finds the entity blocking a tile, other than `entity` itself.
+    pub fn blocker(&self, tile_pos: &IVec2, entity: Entity) -> Option<Entity> {
+        self.get(tile_pos)
+            .copied()
+            .filter(|occupant| *occupant != entity)
+    }
+
+    /// Reserves the destination of an action before starting it.
+    ///
+    /// # Parameters
+    /// - `entity`: The entity starting the action.
+    /// - `action`: The action about to start.
+    /// - `position`: The tile position of the entity.
+    ///
+    /// # Returns
+    /// The entity occupying the destination if the action is blocked.
+    pub fn reserve(
+        &mut self,
+        entity: Entity,
+        action: &Action,
+        position: &IVec2,
+    ) -> Result<(), Entity> {
+        if !action.kind.is_moving() {
+            return Ok(());
+        }
+
+        let target_pos = *position + action.get_raw_transformation();
+        match self.blocker(&target_pos, entity) {
+            Some(blocker) => Err(blocker),
+            None => {
+                self.occupy(target_pos, entity);
+                Ok(())
+            }
+        }
+    }
+}
+
 impl ChunkMap {
     /// Creates a new `ChunkMap`.
     pub fn new() -> Self {
         Self(HashMap::new())
     }
 }
+
+impl<'w, 's> Terrain<'w, 's> {
+    /// Retrieves the relief level of a tile.
+    ///
+    /// # Parameters
+    /// - `tile_pos`: The tile position.
+    ///
+    /// # Returns
+    /// The relief level, or `None` if the tile is not spawned yet.
+    pub fn level(&self, tile_pos: &IVec2) -> Option<u32> {
+        self.tile(tile_pos).map(|(level, _)| level)
+    }
+
+    /// Retrieves the direction going down the ramp of a tile.
+    ///
+    /// # Parameters
+    /// - `tile_pos`: The tile position.
+    ///
+    /// # Returns
+    /// The downhill direction, or `None` if the tile is not a spawned ramp.
+    pub fn ramp(&self, tile_pos: &IVec2) -> Option<ActionDirection> {
+        self.tile(tile_pos).and_then(|(_, ramp)| ramp)
+    }
+
+    /// Retrieves the relief level and ramp of a tile.
+    fn tile(&self, tile_pos: &IVec2) -> Option<(u32, Option<ActionDirection>)> {
+        let (layer, _) = self.chunk_map.get(&tile_pos_to_chunk_pos(tile_pos))?;
+        let tile_storage = self.chunk_query.get(*layer).ok()?;
+        let tile_entity = tile_storage.get(&relative_tile_pos(tile_pos))?;
+        self.tile_query
+            .get(tile_entity)
+            .ok()
+            .map(|(level, ramp)| (**level, ramp.map(|ramp| (**ramp).clone())))
+    }
+
+    /// Determines if an action is possible from a tile.
+    ///
+    /// # Parameters
+    /// - `action`: The action to be evaluated.
+    /// - `position`: The tile position of the entity.
+    ///
+    /// # Returns
+    /// Returns `true` if the action is possible, `false` otherwise.
+    ///
+    /// Moving actions are only possible between spawned tiles of the same level,
+    /// straight along a ramp or a shore to go up exactly one level, or straight down
+    /// any number of levels, falling unless along a ramp or a shore, see `fall_height`.
+    /// Water is only enterable when shallow, see `is_shallow`.
+    /// Diagonal moves cannot cut corners, both orthogonal neighbours must be passable.
+    pub fn is_action_possible(&self, action: &Action, position: &IVec2) -> bool {
+        if !action.kind.is_moving() {
+            return true;
+        }
+
+        let Some(level) = self.level(position) else {
+            return false;
+        };
+        let transformation = action.get_raw_transformation();
+        let target_pos = *position + transformation;
+        let Some(target_level) = self.level(&target_pos) else {
+            return false;
+        };
+        if target_level == WATER_LEVEL && !self.is_shallow(&target_pos) {
+            return false;
+        }
+
+        if target_level != level {
+            let direction = &action.direction;
+            let is_shore = level.min(target_level) == WATER_LEVEL;
+            return !direction.is_diagonal()
+                && (target_level < level
+                    || (target_level == level + 1
+                        && (is_shore || self.ramp(&target_pos) == Some(direction.opposite()))));
+        }
+
+        let is_passable = |offset: IVec2| self.level(&(*position + offset)) == Some(level);
+        is_passable(transformation)
+            && (!action.direction.is_diagonal()
+                || (is_passable(IVec2::new(transformation.x, 0))
+                    && is_passable(IVec2::new(0, transformation.y))))
+    }
+
+    /// Computes the number of levels fallen when going from a tile to another.
+    ///
+    /// # Parameters
+    /// - `from`: The tile position left.
+    /// - `to`: The tile position reached.
+    ///
+    /// # Returns
+    /// The height of the fall, 0 when not going down or going down a ramp or a shore.
+    pub fn fall_height(&self, from: &IVec2, to: &IVec2) -> u32 {
+        let (Some(from_level), Some(to_level)) = (self.level(from), self.level(to)) else {
+            return 0;
+        };
+        if to_level >= from_level {
+            return 0;
+        }
+
+        let height = from_level - to_level;
+        let is_shore = to_level == WATER_LEVEL;
+        let is_ramp = ActionDirection::from_vector(*to - *from)
+            .is_some_and(|direction| self.ramp(from) == Some(direction));
+        if height == 1 && (is_shore || is_ramp) {
+            0
+        } else {
+            height
+        }
+    }
+
+    /// Determines if an entity standing on a tile can overlap another tile.
+    ///
+    /// # Parameters
+    /// - `from`: The tile position of the entity.
+    /// - `tile_pos`: The tile position to overlap.
+    ///
+    /// # Returns
+    /// Returns `true` if the tiles are the same or a walking step between them is possible.
+    pub fn can_enter(&self, from: &IVec2, tile_pos: &IVec2) -> bool {
+        let offset = *tile_pos - *from;
+        if offset == IVec2::ZERO {
+            return true;
+        }
+        if offset.abs().max_element() > 1 {
+            return false;
+        }
+        let Some(direction) = ActionDirection::from_vector(offset) else {
+            return false;
+        };
+        self.is_action_possible(&Action::new(ActionKind::Walk, direction), from)
+    }
+
+    /// Checks if a water tile is close enough to the land to swim in it.
+    ///
+    /// # Parameters
+    /// - `tile_pos`: The tile position.
+    ///
+    /// # Returns
+    /// Returns `true` if a spawned land tile is within `SWIM_SHORE_DISTANCE` tiles.
+    pub fn is_shallow(&self, tile_pos: &IVec2) -> bool {
+        let range = -SWIM_SHORE_DISTANCE..=SWIM_SHORE_DISTANCE;
+        range.clone().any(|x| {
+            range.clone().any(|y| {
+                self.level(&(*tile_pos + IVec2::new(x, y)))
+                    .is_some_and(|level| level != WATER_LEVEL)
+            })
+        })
+    }
+
+    /// Retrieves the duration factor of moving to a tile.
+    ///
+    /// # Parameters
+    /// - `tile_pos`: The tile position.
+    ///
+    /// # Returns
+    /// The factor from `TERRAIN_COST_MAP`, 1 if the tile is not spawned yet.
+    pub fn cost(&self, tile_pos: &IVec2) -> f32 {
+        self.level(tile_pos).map_or(1., |level| {
+            TERRAIN_COST_MAP.lookup(TileKind::from_level(level))
+        })
+    }
+
+    /// Prepares an action before starting it.
+    ///
+    /// # Parameters
+    /// - `action`: The requested action.
+    /// - `position`: The tile position of the entity.
+    ///
+    /// # Returns
+    /// The action to start with the cost of its destination, or `None` if impossible.
+    ///
+    /// Moving actions become swimming in water and walking back on land.
+    pub fn plan_action(&self, mut action: Action, position: &IVec2) -> Option<(Action, f32)> {
+        if !action.kind.is_moving() {
+            return Some((action, 1.));
+        }
+        if !self.is_action_possible(&action, position) {
+            return None;
+        }
+
+        let target_pos = *position + action.get_raw_transformation();
+        if self.level(&target_pos) == Some(WATER_LEVEL) {
+            action.kind = ActionKind::Swim;
+        } else if action.kind == ActionKind::Swim {
+            action.kind = ActionKind::Walk;
+        }
+        Some((action, self.cost(&target_pos)))
+    }
+
+    /// Finds the shortest walkable path ending within `distance` tiles of `target`, without falls.
+    ///
+    /// # Parameters
+    /// - `start`: The tile position of the entity.
+    /// - `target`: The tile position to get close to.
+    /// - `distance`: The distance to keep from the target, in tiles.
+    /// - `radius`: The search never goes further than `radius` tiles from `start`.
+    /// - `is_blocked`: Tells which tiles are occupied and must be avoided.
+    ///
+    /// # Returns
+    /// The directions to walk, empty if already close enough, or `None` if unreachable.
+    pub fn find_path(
+        &self,
+        start: IVec2,
+        target: IVec2,
+        distance: i32,
+        radius: i32,
+        is_blocked: impl Fn(&IVec2) -> bool,
+    ) -> Option<Vec<ActionDirection>> {
+        let remaining = |position: &IVec2| {
+            ((*position - target).abs().max_element() - distance).max(0) as u32 * PATH_STRAIGHT_COST
+        };
+
+        let (path, _) = astar(
+            &start,
+            |position| {
+                ActionDirection::iter()
+                    .map(|direction| Action::new(ActionKind::Walk, direction))
+                    .filter(|action| {
+                        self.is_action_possible(action, position)
+                            && self.fall_height(
+                                position,
+                                &(*position + action.get_raw_transformation()),
+                            ) == 0
+                    })
+                    .map(|action| {
+                        let next = *position + action.get_raw_transformation();
+                        let cost = if action.direction.is_diagonal() {
+                            PATH_DIAGONAL_COST
+                        } else {
+                            PATH_STRAIGHT_COST
+                        };
+                        (next, (cost as f32 * self.cost(&next)).round() as u32)
+                    })
+                    .filter(|(next, _)| {
+                        (*next - start).abs().max_element() <= radius && !is_blocked(next)
+                    })
+                    .collect::<Vec<_>>()
+            },
+            remaining,
+            |position| remaining(position) == 0,
+        )?;
+
+        Some(
+            path.windows(2)
+                .filter_map(|step| ActionDirection::from_vector(step[1] - step[0]))
+                .collect(),
+        )
+    }
+}
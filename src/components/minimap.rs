@@ -0,0 +1,17 @@
+use bevy::prelude::*;
+
+/// Component indicating that a UI image shows the minimap around the user.
+#[derive(Component)]
+pub struct Minimap;
+
+/// Component indicating that a UI image shows the full world map.
+#[derive(Component)]
+pub struct WorldMap;
+
+/// Something shown on the maps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MapMarker {
+    User,
+    Bot,
+    Place,
+}
@@ -4,13 +4,39 @@
 pub mod action;
 // Manages animations within the game.
 pub mod animation;
+// Manages the offline bot brains.
+pub mod brain;
 // Manages characters and entities in the game.
 pub mod character;
+// Manages the in-game time.
+pub mod clock;
+// Manages the explored tiles.
+pub mod exploration;
+// Manages attacks, blocks and knockbacks.
+pub mod combat;
+// Manages commands given to bots.
+pub mod command;
 // Manages display and user interface components.
 pub mod display;
 // Manages AI and chatbot functionality.
 pub mod gpt;
+// Manages the mapping of devices to input actions.
+pub mod input;
+// Manages the interactions between characters and objects.
+pub mod interaction;
+// Manages items and inventories.
+pub mod item;
 // Manages the game map and tilesets.
 pub mod map;
+// Manages the minimap and the world map.
+pub mod minimap;
+// Manages the quests proposed by bots.
+pub mod quest;
+// Manages the generated structures and points of interest.
+pub mod structure;
 // Manages textures and image assets.
 pub mod texture;
+// Manages the weather and its effects.
+pub mod weather;
+// Manages the wildlife creatures.
+pub mod wildlife;
@@ -0,0 +1,79 @@
+use bevy::prelude::*;
+
+use crate::constants::item::ITEM_REGISTRY;
+
+/// What must be done to fulfil a quest objective.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Objective {
+    /// Stands on or next to a tile.
+    ///
+    /// Proposed relative to the giver, the tile is absolute once validated.
+    Reach(IVec2),
+    /// Carries a number of items.
+    Collect { item: String, count: u32 },
+    /// Interacts with a named character.
+    TalkTo(String),
+}
+
+/// Quest proposed by a bot, before being validated against the world.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuestProposal {
+    pub title: String,
+    pub objectives: Vec<Objective>,
+}
+
+/// Objective of a quest, and whether it is fulfilled.
+#[derive(Debug, Clone)]
+pub struct QuestObjective {
+    pub objective: Objective,
+    pub is_done: bool,
+}
+
+/// Quest given to the user by a bot.
+#[derive(Debug, Clone)]
+pub struct Quest {
+    pub title: String,
+    /// The bot proposing the quest.
+    pub giver: Entity,
+    pub objectives: Vec<QuestObjective>,
+}
+
+/// Resource holding the quests of the user, completed quests are removed.
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct QuestLog(pub Vec<Quest>);
+
+/// Event sent when a bot proposes a quest.
+#[derive(Event, Debug, Clone)]
+pub struct QuestProposalEvent {
+    pub giver: Entity,
+    pub proposal: QuestProposal,
+}
+
+impl Objective {
+    /// Describes the objective, positions being relative to `origin`.
+    pub fn describe(&self, origin: IVec2) -> String {
+        match self {
+            Objective::Reach(tile_pos) => {
+                let relative_position = *tile_pos - origin;
+                format!(
+                    "reach the tile {} {} from you",
+                    relative_position.x, relative_position.y
+                )
+            }
+            Objective::Collect { item, count } => {
+                let name = ITEM_REGISTRY
+                    .get(item)
+                    .map_or(item.as_str(), |definition| definition.name);
+                format!("collect {} {}", count, name)
+            }
+            Objective::TalkTo(name) => format!("talk to {}", name),
+        }
+    }
+}
+
+impl Quest {
+    /// Checks if every objective is fulfilled.
+    pub fn is_complete(&self) -> bool {
+        self.objectives.iter().all(|objective| objective.is_done)
+    }
+}
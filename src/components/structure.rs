@@ -0,0 +1,80 @@
+use std::fmt;
+
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+/// Kind of structure, shown to the bots.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StructureKind {
+    Ruins,
+    Campfire,
+    Village,
+    Signpost,
+}
+
+/// Describes a structure placed by the generation.
+pub struct StructureTemplate {
+    pub kind: StructureKind,
+    /// Relative chance of being placed among the templates.
+    pub weight: u32,
+    /// Names given to the placed structures.
+    pub names: &'static [&'static str],
+    /// Tiles of the structure, from the top row, see `StructurePiece::from_char`.
+    pub rows: &'static [&'static str],
+}
+
+/// Part of a structure standing on a single tile.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StructurePiece {
+    Wall,
+    House,
+    Campfire,
+    /// Sign telling the name of the structure, or the way to the closest one.
+    Sign,
+}
+
+/// A named place the bots know about.
+#[derive(Debug, Clone)]
+pub struct PointOfInterest {
+    pub name: String,
+    pub kind: StructureKind,
+    /// Tile at the center of the structure.
+    pub center: IVec2,
+}
+
+/// Resource registering the points of interest of the generated regions.
+///
+/// Points of interest are never removed, the bots remember the places they saw.
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct PointsOfInterest(pub HashMap<IVec2, PointOfInterest>);
+
+/// Resource remembering the structure pieces spawned by each chunk.
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct ChunkStructures(pub HashMap<IVec2, Vec<Entity>>);
+
+impl StructurePiece {
+    /// Reads a template tile, `None` for the empty ones.
+    ///
+    /// `#` is a wall, `H` a house, `F` a campfire and `S` a sign. Empty tiles
+    /// are `.` when they must be flat ground, and ` ` when anything goes.
+    pub fn from_char(tile: char) -> Option<Self> {
+        match tile {
+            '#' => Some(StructurePiece::Wall),
+            'H' => Some(StructurePiece::House),
+            'F' => Some(StructurePiece::Campfire),
+            'S' => Some(StructurePiece::Sign),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for StructureKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StructureKind::Ruins => write!(f, "ruins"),
+            StructureKind::Campfire => write!(f, "campfire"),
+            StructureKind::Village => write!(f, "village"),
+            StructureKind::Signpost => write!(f, "signpost"),
+        }
+    }
+}
@@ -0,0 +1,218 @@
+use std::fmt;
+
+use bevy::prelude::*;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use crate::constants::weather::{WEATHERS, WEATHER_PERIOD, WEATHER_SEED};
+use crate::util::noise::hash_position;
+
+use super::clock::WorldClock;
+use super::wildlife::Biome;
+
+/// Kind of weather, see `WEATHERS` for their effects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WeatherKind {
+    Clear,
+    Rain,
+    Storm,
+    Fog,
+    Snow,
+}
+
+/// Particles falling across the screen.
+pub struct ParticleDefinition {
+    /// Number of particles on screen.
+    pub count: usize,
+    pub size: Vec2, // unit: pixels
+    /// Speed of the particles, the horizontal one varying by up to `drift`.
+    pub velocity: Vec2, // unit: screen percents per second
+    pub drift: f32, // unit: screen percents per second
+    pub color: Color,
+}
+
+/// Sound looping while a weather lasts.
+pub struct WeatherSoundDefinition {
+    pub path: &'static str,
+    /// Volume relative to the global volume.
+    pub volume: f32,
+}
+
+/// Describes a kind of weather.
+pub struct WeatherDefinition {
+    /// Biomes under the user the weather can start in.
+    pub biomes: &'static [Biome],
+    /// Weathers following this one, with their relative chances.
+    pub transitions: &'static [(WeatherKind, u32)],
+    /// Said by the bots without a model when the weather starts.
+    pub remark: &'static str,
+    /// Speed factor of moving characters.
+    pub movement_factor: f32,
+    /// Color laid over the world.
+    pub overlay: Color,
+    pub particles: Option<ParticleDefinition>,
+    pub sound: Option<WeatherSoundDefinition>,
+}
+
+/// Resource holding the current weather.
+///
+/// The weather changes at the start of every period of `WEATHER_PERIOD`
+/// in-game hours. The next weather only depends on the current one, the
+/// period and the biome under the user, so the same world replays the same
+/// weather.
+#[derive(Resource)]
+pub struct Weather {
+    pub kind: WeatherKind,
+    /// Period the current weather started in.
+    pub period: u64,
+}
+
+/// Event sent when the weather changes, starting its sound and the bots reactions.
+#[derive(Event)]
+pub struct WeatherChangeEvent {
+    pub previous: WeatherKind,
+    pub current: WeatherKind,
+}
+
+/// Component indicating that a UI node holds the weather overlay and particles.
+#[derive(Component)]
+pub struct WeatherLayer;
+
+/// Component indicating that an entity plays the sound of the current weather.
+#[derive(Component)]
+pub struct WeatherSound;
+
+/// Component representing a weather particle on screen.
+#[derive(Component)]
+pub struct WeatherParticle {
+    pub position: Vec2, // unit: screen percents
+    pub velocity: Vec2, // unit: screen percents per second
+}
+
+impl WeatherKind {
+    /// Gets the definition of the weather.
+    pub fn definition(self) -> &'static WeatherDefinition {
+        &WEATHERS[self as usize]
+    }
+
+    /// Picks the weather of the next period.
+    ///
+    /// # Parameters
+    /// - `biome`: The biome under the user.
+    /// - `period`: The period starting.
+    ///
+    /// # Returns
+    /// The weather starting, the same for the same arguments.
+    pub fn next(self, biome: Biome, period: u64) -> WeatherKind {
+        let mut rng = StdRng::seed_from_u64(hash_position(
+            WEATHER_SEED,
+            IVec2::new(period as i32, (period >> 32) as i32),
+        ));
+        let candidates: Vec<_> = self
+            .definition()
+            .transitions
+            .iter()
+            .filter(|(kind, _)| kind.definition().biomes.contains(&biome))
+            .collect();
+        WeightedIndex::new(candidates.iter().map(|(_, weight)| weight))
+            .map_or(WeatherKind::Clear, |dist| {
+                candidates[dist.sample(&mut rng)].0
+            })
+    }
+}
+
+impl Weather {
+    /// Gets the weather period of an in-game time.
+    pub fn period_at(clock: &WorldClock) -> u64 {
+        ((clock.day as f32 * 24. + clock.hour) / WEATHER_PERIOD) as u64
+    }
+}
+
+impl Default for Weather {
+    fn default() -> Self {
+        Self {
+            kind: WeatherKind::Clear,
+            period: Weather::period_at(&WorldClock::default()),
+        }
+    }
+}
+
+impl fmt::Display for WeatherKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WeatherKind::Clear => write!(f, "clear"),
+            WeatherKind::Rain => write!(f, "rain"),
+            WeatherKind::Storm => write!(f, "storm"),
+            WeatherKind::Fog => write!(f, "fog"),
+            WeatherKind::Snow => write!(f, "snow"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BIOMES: [Biome; 3] = [Biome::Shore, Biome::Meadow, Biome::Forest];
+    const KINDS: [WeatherKind; 5] = [
+        WeatherKind::Clear,
+        WeatherKind::Rain,
+        WeatherKind::Storm,
+        WeatherKind::Fog,
+        WeatherKind::Snow,
+    ];
+
+    fn sequence(biome: Biome, periods: u64) -> Vec<WeatherKind> {
+        let mut kind = WeatherKind::Clear;
+        (1..=periods)
+            .map(|period| {
+                kind = kind.next(biome, period);
+                kind
+            })
+            .collect()
+    }
+
+    #[test]
+    fn next_replays_the_same_sequence() {
+        for biome in BIOMES {
+            assert_eq!(sequence(biome, 100), sequence(biome, 100));
+        }
+    }
+
+    #[test]
+    fn next_changes_over_the_periods() {
+        for biome in BIOMES {
+            let kinds = sequence(biome, 100);
+            assert!(kinds.iter().any(|kind| *kind != kinds[0]));
+        }
+    }
+
+    #[test]
+    fn next_stays_in_the_biome() {
+        for biome in BIOMES {
+            for kind in KINDS {
+                for period in 0..100 {
+                    let next = kind.next(biome, period);
+                    assert!(
+                        next.definition().biomes.contains(&biome),
+                        "{} after {} in {:?}",
+                        next,
+                        kind,
+                        biome
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn period_at_counts_whole_periods() {
+        let period_at = |day, hour| Weather::period_at(&WorldClock { day, hour });
+        assert_eq!(period_at(0, 0.), 0);
+        assert_eq!(period_at(0, WEATHER_PERIOD - 0.01), 0);
+        assert_eq!(period_at(0, WEATHER_PERIOD), 1);
+        assert_eq!(period_at(1, 0.), (24. / WEATHER_PERIOD) as u64);
+        assert_eq!(period_at(1, 0.), period_at(0, 24.));
+    }
+}
@@ -0,0 +1,105 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use once_cell::sync::Lazy;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use crate::constants::wildlife::WILDLIFE_THINK_INTERVAL;
+
+use super::animation::{ActionAnimationMap, AnimationSpriteGrid};
+
+/// Kind of land a creature lives on, derived from the relief level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Biome {
+    /// Soil around the water.
+    Shore,
+    /// Grass.
+    Meadow,
+    /// Dark grass.
+    Forest,
+}
+
+/// How a creature reacts to characters in sight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Temperament {
+    /// Flees from characters.
+    Passive,
+    /// Chases and attacks characters.
+    Hostile,
+}
+
+/// Describes a kind of creature.
+pub struct CreatureDefinition {
+    pub name: &'static str,
+    pub sprite: &'static str,
+    pub sprite_grid: AnimationSpriteGrid,
+    pub animations: &'static Lazy<ActionAnimationMap>,
+    /// Biomes the creature spawns in.
+    pub biomes: &'static [Biome],
+    /// Relative chance of spawning among the creatures of a biome.
+    pub weight: u32,
+    pub health: u8,
+    pub temperament: Temperament,
+    /// Distance from which characters are noticed.
+    pub sight: i32, // unit: tiles
+    /// Damage of an attack, hostile creatures only.
+    pub damage: u8,
+    pub attack_cooldown: f32, // unit: seconds
+}
+
+/// What a creature is currently doing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CreatureState {
+    /// Roams around its spawn point.
+    Wander,
+    /// Runs away from a character.
+    Flee(Entity),
+    /// Walks toward a character.
+    Chase(Entity),
+    /// Hits an adjacent character.
+    Attack(Entity),
+}
+
+/// Component driving a wildlife creature.
+#[derive(Component)]
+pub struct Creature {
+    pub species: &'static CreatureDefinition,
+    pub state: CreatureState,
+    /// Seeded from the chunk, for reproducible decisions.
+    pub rng: StdRng,
+    pub think_timer: Timer,
+    pub attack_timer: Timer,
+}
+
+/// Resource remembering the creatures spawned by each chunk.
+///
+/// Chunks are only populated once their tiles are generated, creatures are
+/// despawned together with the chunk they were spawned by.
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct ChunkCreatures(pub HashMap<IVec2, Vec<Entity>>);
+
+impl Biome {
+    /// Finds the biome of a relief level, `None` for water.
+    pub fn from_level(level: u32) -> Option<Self> {
+        match level {
+            0 => None,
+            1 => Some(Biome::Shore),
+            2..=4 => Some(Biome::Meadow),
+            _ => Some(Biome::Forest),
+        }
+    }
+}
+
+impl Creature {
+    pub fn new(species: &'static CreatureDefinition, seed: u64) -> Self {
+        let mut attack_timer = Timer::from_seconds(species.attack_cooldown, TimerMode::Once);
+        attack_timer.tick(attack_timer.duration());
+        Self {
+            species,
+            state: CreatureState::Wander,
+            rng: StdRng::seed_from_u64(seed),
+            think_timer: Timer::from_seconds(WILDLIFE_THINK_INTERVAL, TimerMode::Repeating),
+            attack_timer,
+        }
+    }
+}
@@ -1,10 +1,23 @@
 use crate::components::action::*;
+use crate::constants::map::TILE;
 use phf::phf_map;
-use std::time::Duration;
 
-pub const ACTION_TICK_FREQUENCY: Duration = Duration::from_millis(20);
 pub const WALK_RATE: f32 = 1.;
 pub const RUN_RATE: f32 = 1.;
+pub const SWIM_RATE: f32 = 1.;
+pub const KNOCKBACK_RATE: f32 = 1.;
+// Diagonal moves cover a longer distance
+pub const DIAGONAL_DURATION_FACTOR: f32 = std::f32::consts::SQRT_2;
+// Easing of the tile steps, 0 is linear and 1 is smoothstep
+pub const MOVEMENT_EASING: f32 = 0.3;
+
+// FREE MOVEMENT
+// Same speeds as the tile steps
+pub const FREE_WALK_SPEED: f32 = TILE / 0.3; // unit: pixels per second
+pub const FREE_RUN_SPEED: f32 = TILE / 0.2; // unit: pixels per second
+
+pub const FREE_ACCELERATION: f32 = FREE_RUN_SPEED * 8.; // unit: pixels per second squared
+pub const FREE_DECELERATION: f32 = FREE_RUN_SPEED * 12.; // unit: pixels per second squared
 
 // PLAYER VALUES
 pub const PLAYER_ACTION_DEFAULT: Action = Action::new(ActionKind::Stand, ActionDirection::Down);
@@ -14,4 +27,50 @@ pub const PLAYER_ACTION_DURATION_MAP: ActionDurationPHF = ActionDurationPHF(phf_
     "Walk" => 0.3,
     "Run" => 0.2,
     "Type" => 0.0,
+    "Swim" => 0.3,
+    "Interact" => 0.3,
+    "Attack" => 0.4,
+    "AttackHit" => 0.15,
+    "Block" => 0.5,
+    "Knockback" => 0.2,
+});
+
+// Bots move like players, but their idle emotes last a while
+pub const BOT_ACTION_DURATION_MAP: ActionDurationPHF = ActionDurationPHF(phf_map! {
+    "Stand" => 0.0,
+    "Walk" => 0.3,
+    "Run" => 0.2,
+    "Type" => 2.0,
+    "Swim" => 0.3,
+    "Interact" => 0.3,
+    "Attack" => 0.4,
+    "AttackHit" => 0.15,
+    "Block" => 0.5,
+    "Knockback" => 0.2,
 });
+
+// Creatures walk slower than players but run almost as fast
+pub const CREATURE_ACTION_DURATION_MAP: ActionDurationPHF = ActionDurationPHF(phf_map! {
+    "Stand" => 0.0,
+    "Walk" => 0.45,
+    "Run" => 0.25,
+    "Type" => 2.0,
+    "Swim" => 0.5,
+    "Interact" => 0.3,
+    "Attack" => 0.5,
+    "AttackHit" => 0.25,
+    "Block" => 0.5,
+    "Knockback" => 0.25,
+});
+
+// TERRAIN
+// Action duration factor when moving to a tile, by tile kind
+pub const TERRAIN_COST_MAP: TerrainCostPHF = TerrainCostPHF(phf_map! {
+    "Water" => 1.7,
+    "Soil" => 1.0,
+    "Grass" => 1.0,
+    "DarkGrass" => 1.25,
+});
+
+// Water is only shallow enough to swim that close to the land
+pub const SWIM_SHORE_DISTANCE: i32 = 2; // unit: tiles
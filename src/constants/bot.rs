@@ -1,8 +1,128 @@
+use crate::components::action::ActionKind;
+use crate::components::gpt::BotDefinition;
+use crate::constants::character::*;
+use crate::constants::sprites::PLAYER_SPRITE;
+
 pub const BOT_VIEW_DISTANCE: i32 = 32; // unit: tiles
 
+// Known places that close are described to the bots
+pub const BOT_PLACE_DISTANCE: i32 = 64; // unit: tiles
+
+// Maximum number of GPT requests in flight, all bots included
+pub const BOT_MAX_CONCURRENT_REQUESTS: usize = 2;
+// Delay before asking an unavailable model again
+pub const BOT_RETRY_DELAY: f32 = 30.; // unit: seconds
+
+// Maximum number of messages exchanged by two bots before they stop listening
+pub const BOT_MAX_DIALOGUE_TURNS: u32 = 6;
+// Silence needed before two bots can talk again
+pub const BOT_DIALOGUE_COOLDOWN: f32 = 30.; // unit: seconds
+
 pub const COMMANDS: &str = "\
 Reply nothing else than with text commands. One command per line.
 Available commands:
-walk up/down/left/right times: walk left 5";
+walk/run up/down/left/right/up-left/up-right/down-left/down-right times: walk left 5
+attack/block up/down/left/right, hits or guards the faced tile: attack left
+attack name, chases and attacks it until it is gone: attack Wolf
+say to name \"message\": say to Biscuit \"Hello!\"
+face up/down/left/right: face left
+interact up/down/left/right, with what you face: interact left
+pickup: picks up the items under you and in front of you
+drop item count: drop apple 1
+give item count to name, when close: give apple 1 to Player
+use item, to heal: use apple
+wait seconds: wait 1.5
+goto x y, relative to you: goto 3 -2
+follow name distance: follow Player 2
+stop: stops following or going somewhere
+repeat count { commands }: repeat 2 { walk left 1 }
+propose_quest \"title\" { objectives }, one objective per line among
+reach x y (relative to you), collect item count, talk to name:
+propose_quest \"Picnic\" {
+collect apple 3
+talk to Biscuit
+}
+# starts a comment";
+
+pub const CONTEXT: &str = "\
+Mission: Go to player position.
+Propose quests to the player from time to time, using what you see around you.";
+
+// COMMAND LANGUAGE
+// Actions the bots can ask for, the others are only caused by the game
+pub const COMMAND_ACTION_KINDS: [ActionKind; 5] = [
+    ActionKind::Walk,
+    ActionKind::Run,
+    ActionKind::Interact,
+    ActionKind::Attack,
+    ActionKind::Block,
+];
+// Maximum number of commands in a single text
+pub const COMMAND_MAX_COUNT: usize = 64;
+pub const COMMAND_MAX_REPEAT: usize = 10;
+pub const COMMAND_MAX_TIMES: usize = 20;
+pub const COMMAND_MAX_WAIT: f32 = 10.; // unit: seconds
+
+// PATHS
+// Delay before searching a path to an unreachable or blocked tile again
+pub const BOT_PATH_RETRY_DELAY: f32 = 1.; // unit: seconds
+
+// FOLLOW
+// Distance kept when not given
+pub const FOLLOW_DEFAULT_DISTANCE: i32 = 2; // unit: tiles
+
+// Path length from which the bot runs
+pub const FOLLOW_RUN_DISTANCE: usize = 6; // unit: tiles
+
+// OFFLINE BRAIN
+pub const BRAIN_THINK_INTERVAL: f32 = 0.5; // unit: seconds
+
+// Distance kept from the player
+pub const BRAIN_FOLLOW_DISTANCE: i32 = 3; // unit: tiles
+
+// Hostile creatures that close to the bot or the player are attacked
+pub const BRAIN_DEFEND_DISTANCE: i32 = 4; // unit: tiles
+
+// Maximum number of steps decided at once
+pub const BRAIN_MAX_STEPS: usize = 3;
+pub const BRAIN_WANDER_CHANCE: f64 = 0.2;
+pub const BRAIN_EMOTE_CHANCE: f64 = 0.05;
+
+// MEMORY
+// Estimated history size triggering a summary
+pub const MEMORY_SUMMARY_THRESHOLD: usize = 1500; // unit: tokens
+
+// Most recent turns never summarized
+pub const MEMORY_KEPT_TURNS: usize = 6;
+// Maximum number of facts remembered
+pub const MEMORY_MAX_FACTS: usize = 10;
+// Average number of characters in a token
+pub const MEMORY_CHARACTERS_PER_TOKEN: usize = 4;
+
+pub const MEMORY_SUMMARY_PROMPT: &str = "\
+You manage the memory of a game companion.
+Summarize the conversation below in a few sentences, starting with \"SUMMARY:\".
+Then list the important facts worth remembering, one per line, each starting with \"FACT:\".
+Example: FACT: player likes the lake to the north";
+
+pub const DEFAULT_MODEL: &str = "gpt-3.5-turbo-1106";
+
+pub const DEFAULT_API_URL: &str = "https://api.openai.com/v1/chat/completions";
 
-pub const CONTEXT: &str = "Mission: Go to player position.";
+// All the bots spawned at startup
+pub const BOT_DEFINITIONS: [BotDefinition; 2] = [
+    BotDefinition {
+        name: "Mittens",
+        spawn: MITTENS_SPAWN,
+        persona: "You are Mittens, a curious and cheerful cat who loves exploring.",
+        model: None,
+        sprite: PLAYER_SPRITE,
+    },
+    BotDefinition {
+        name: "Biscuit",
+        spawn: BISCUIT_SPAWN,
+        persona: "You are Biscuit, a calm and careful cat who likes to stay close to friends.",
+        model: None,
+        sprite: PLAYER_SPRITE,
+    },
+];
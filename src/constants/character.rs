@@ -5,7 +5,39 @@ use super::map::TILE;
 
 // PLAYERS
 pub const PLAYER_HEALTH: Health = Health(100);
+
+// DAMAGE
+pub const FALL_DAMAGE: u8 = 10; // unit: health per level
+
+// Time spent in water before drowning
+pub const SWIM_ENDURANCE: f32 = 8.; // unit: seconds
+pub const DROWNING_INTERVAL: f32 = 1.; // unit: seconds
+pub const DROWNING_DAMAGE: u8 = 5;
+
+pub const INVULNERABILITY_DURATION: f32 = 1.; // unit: seconds
+
+// Period of the blinking of invulnerable characters
+pub const INVULNERABILITY_BLINK: f32 = 0.1; // unit: seconds
+pub const INVULNERABILITY_ALPHA: f32 = 0.3;
+
+pub const RESPAWN_DELAY: f32 = 3.; // unit: seconds
+
+// COMBAT
+pub const PLAYER_ATTACK_DAMAGE: u8 = 10;
+
 // User
+pub const USER_NAME: &str = "Player";
 pub const USER_SPAWN: Vec2 = Vec2::new(TILE * 0., TILE * 0.);
+// Half extents of the collision box used by the free movement
+pub const USER_HITBOX: Vec2 = Vec2::new(TILE * 0.3, TILE * 0.2); // unit: pixels
+
+// Clicked tiles further away are ignored
+pub const USER_PATH_RADIUS: i32 = 32; // unit: tiles
+
+// Path length from which the user runs to the clicked tile
+pub const USER_PATH_RUN_DISTANCE: usize = 6; // unit: tiles
+
 // Mittens
 pub const MITTENS_SPAWN: Vec2 = Vec2::new(TILE * 4., TILE * 0.);
+// Biscuit
+pub const BISCUIT_SPAWN: Vec2 = Vec2::new(TILE * -4., TILE * 0.);
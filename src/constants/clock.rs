@@ -0,0 +1,27 @@
+use bevy::prelude::*;
+
+// Real duration of a whole in-game day
+pub const CLOCK_DAY_DURATION: f32 = 600.; // unit: seconds
+pub const CLOCK_START_HOUR: f32 = 8.; // unit: hours
+
+// Start of each part of the day
+pub const DAWN_HOUR: f32 = 5.; // unit: hours
+pub const DAY_HOUR: f32 = 8.; // unit: hours
+pub const DUSK_HOUR: f32 = 18.; // unit: hours
+pub const NIGHT_HOUR: f32 = 21.; // unit: hours
+
+// Color laid over the world at each hour, blended in between
+pub const DAYLIGHT_TINTS: [(f32, Color); 8] = [
+    (0., NIGHT_TINT),
+    (DAWN_HOUR, NIGHT_TINT),
+    (6.5, DAWN_TINT),
+    (DAY_HOUR, DAY_TINT),
+    (DUSK_HOUR, DAY_TINT),
+    (19.5, DUSK_TINT),
+    (NIGHT_HOUR, NIGHT_TINT),
+    (24., NIGHT_TINT),
+];
+pub const DAWN_TINT: Color = Color::rgba(1., 0.6, 0.4, 0.2);
+pub const DAY_TINT: Color = Color::rgba(1., 1., 1., 0.);
+pub const DUSK_TINT: Color = Color::rgba(0.9, 0.35, 0.2, 0.25);
+pub const NIGHT_TINT: Color = Color::rgba(0.05, 0.05, 0.25, 0.55);
@@ -1,2 +1,32 @@
+use bevy::prelude::*;
+
+use super::map::TILE;
+
 // Virtual pixel for 1 real pixel
 pub const ZOOM: i32 = 3;
+
+// SPEECH
+pub const SPEECH_RELATIVE_POSITION: Vec3 = Vec3::new(0., TILE * 1.5, 10.);
+pub const SPEECH_FONT_SIZE: f32 = 6.;
+pub const SPEECH_WIDTH: f32 = TILE * 8.;
+pub const SPEECH_DURATION: f32 = 4.; // unit: seconds
+
+// TILE CURSOR
+// Between the tilemap layers and the characters
+pub const TILE_CURSOR_LAYER: f32 = 2.;
+pub const TILE_CURSOR_COLOR: Color = Color::rgba(1., 1., 1., 0.35);
+
+// HEALTH BAR
+pub const HEALTH_BAR_WIDTH: f32 = 120.; // unit: pixels
+pub const HEALTH_BAR_HEIGHT: f32 = 10.; // unit: pixels
+pub const HEALTH_BAR_MARGIN: f32 = 8.; // unit: pixels
+pub const HEALTH_BAR_BORDER: f32 = 2.; // unit: pixels
+pub const HEALTH_BAR_BACKGROUND: Color = Color::rgba(0., 0., 0., 0.6);
+pub const HEALTH_BAR_COLOR: Color = Color::rgb(0.85, 0.2, 0.2);
+
+// QUEST LOG
+pub const QUEST_LOG_WIDTH: f32 = 260.; // unit: pixels
+pub const QUEST_LOG_MARGIN: f32 = 8.; // unit: pixels
+pub const QUEST_LOG_PADDING: f32 = 6.; // unit: pixels
+pub const QUEST_LOG_FONT_SIZE: f32 = 14.;
+pub const QUEST_LOG_BACKGROUND: Color = Color::rgba(0., 0., 0., 0.6);
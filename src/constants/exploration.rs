@@ -0,0 +1,16 @@
+use bevy::prelude::*;
+
+// EXPLORATION
+// Tiles that close to users and bots are explored
+pub const EXPLORE_RADIUS: i32 = 10; // unit: tiles
+
+// Color multiplying the unexplored tiles
+pub const UNEXPLORED_TILE_COLOR: Color = Color::rgb(0.4, 0.4, 0.5);
+
+// Unexplored tiles counted by the bots, one every few tiles
+pub const EXPLORE_HINT_DISTANCE: i32 = 48; // unit: tiles
+pub const EXPLORE_HINT_STEP: usize = 4; // unit: tiles
+
+// SAVE
+pub const EXPLORED_SAVE_PATH: &str = "saves/explored.json";
+pub const EXPLORED_SAVE_INTERVAL: f32 = 30.; // unit: seconds
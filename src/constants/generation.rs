@@ -1,3 +1,5 @@
+// Seed of the whole world, every other seed derives from it
+pub const WORLD_SEED: u64 = 0;
 pub const NOISE_ZOOM: f64 = 200.;
 pub const NOISE_OCTAVES: usize = 5;
 pub const NOISE_FREQUENCY: f64 = 0.5;
@@ -13,6 +15,9 @@ pub const SAMPLE_NUMBER: usize = 1;
  * 6: dark_grass
  * 7: dark_grass
  */
+pub const WATER_LEVEL: u32 = 0;
+// Distance between two ramps along a straight edge
+pub const RAMP_SPACING: i32 = 6; // unit: tiles
 pub const MAX_VALUE: f64 = 7.; // Relief
                                // From 0 to 2 (performance reasons)
 pub const LAYER_RANGE: [f64; MAX_VALUE as usize + 2] = [0., 0.8, 0.9, 1.1, 1.25, 1.4, 1.6, 1.8, 2.];
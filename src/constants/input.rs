@@ -0,0 +1,4 @@
+// INPUT CONFIG
+// Read at startup, the embedded copy is used when missing or invalid
+pub const INPUT_CONFIG_PATH: &str = "assets/config/input.json";
+pub const DEFAULT_INPUT_CONFIG: &str = include_str!("../../assets/config/input.json");
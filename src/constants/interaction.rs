@@ -0,0 +1,28 @@
+use bevy::prelude::*;
+
+use super::map::TILE;
+
+// SIGNS
+// The tileset has no signs, they are drawn as plain sprites
+pub const SIGN_SIZE: Vec2 = Vec2::new(TILE * 0.75, TILE * 0.5);
+pub const SIGN_COLOR: Color = Color::rgb(0.6, 0.4, 0.2);
+// Signs spawned at startup, by tile position
+pub const SIGNS: [(IVec2, &str); 1] =
+    [(IVec2::new(0, 2), "Welcome! Press E to interact, T to talk.")];
+
+// CHESTS
+pub const CHEST_SIZE: Vec2 = Vec2::new(TILE * 0.75, TILE * 0.6);
+pub const CHEST_COLOR: Color = Color::rgb(0.8, 0.6, 0.1);
+pub const CHEST_OPEN_COLOR: Color = Color::rgb(0.4, 0.3, 0.1);
+// Chests spawned at startup, by tile position
+pub const CHESTS: [IVec2; 1] = [IVec2::new(2, -2)];
+pub const CHEST_OPEN_TEXT: &str = "The chest opens.";
+pub const CHEST_EMPTY_TEXT: &str = "The chest is empty.";
+pub const CHEST_FULL_TEXT: &str = "Your bag is too full to take anything.";
+
+// PALS
+// Said when interacting with a bot
+pub const TALK_GREETING: &str = "Hi! How are you?";
+
+// Between the tilemap layers and the characters
+pub const INTERACTABLE_LAYER: f32 = 3.;
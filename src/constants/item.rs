@@ -0,0 +1,70 @@
+use bevy::prelude::*;
+use phf::phf_map;
+
+use crate::components::animation::AnimationSpriteGrid;
+use crate::components::item::{ItemDefinition, ItemRegistryPHF, ItemStack};
+
+// INVENTORY
+pub const INVENTORY_SLOTS: usize = 8;
+
+// Items can only be given to characters that close
+pub const ITEM_GIVE_DISTANCE: i32 = 2; // unit: tiles
+
+// REGISTRY
+pub const ITEM_REGISTRY: ItemRegistryPHF = ItemRegistryPHF(phf_map! {
+    "apple" => ItemDefinition {
+        id: "apple",
+        name: "Apple",
+        sprite_index: 0,
+        stack_size: 10,
+        healing: 15,
+        tags: &["food"],
+    },
+    "stone" => ItemDefinition {
+        id: "stone",
+        name: "Stone",
+        sprite_index: 1,
+        stack_size: 20,
+        healing: 0,
+        tags: &["material"],
+    },
+    "stick" => ItemDefinition {
+        id: "stick",
+        name: "Stick",
+        sprite_index: 2,
+        stack_size: 20,
+        healing: 0,
+        tags: &["material"],
+    },
+    "flower" => ItemDefinition {
+        id: "flower",
+        name: "Flower",
+        sprite_index: 3,
+        stack_size: 5,
+        healing: 0,
+        tags: &["gift"],
+    },
+});
+
+// SPRITES
+pub const ITEM_SPRITE: &str = "item/items.png";
+pub const ITEM_SPRITE_GRID: AnimationSpriteGrid = AnimationSpriteGrid {
+    size: Vec2::new(16., 16.),
+    columns: 4,
+    rows: 1,
+    padding: None,
+    offset: None,
+};
+// Between the tilemap layers and the interactable objects
+pub const ITEM_DROP_LAYER: f32 = 2.5;
+
+// WORLD
+// Items lying around at startup, by tile position
+pub const ITEM_DROPS: [(IVec2, ItemStack); 4] = [
+    (IVec2::new(-2, 2), ItemStack::new("apple", 3)),
+    (IVec2::new(3, 1), ItemStack::new("stone", 2)),
+    (IVec2::new(-3, -2), ItemStack::new("flower", 1)),
+    (IVec2::new(1, -3), ItemStack::new("stick", 2)),
+];
+// Found in every chest
+pub const CHEST_LOOT: ItemStack = ItemStack::new("apple", 2);
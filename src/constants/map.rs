@@ -4,6 +4,7 @@ use bevy_ecs_tilemap::map::TilemapTileSize;
 pub const TILE: f32 = 16.;
 pub const TILE_SIZE: TilemapTileSize = TilemapTileSize { x: TILE, y: TILE };
 pub const CHUNK_SIZE: UVec2 = UVec2 { x: 8, y: 8 };
+pub const CHUNK_TILES: usize = (CHUNK_SIZE.x * CHUNK_SIZE.y) as usize;
 pub const CHUNK_SPAWN_RADIUS_X: i32 = 4;
 pub const CHUNK_SPAWN_RADIUS_Y: i32 = 3;
 pub const CHUNK_DESPAWN_RANGE_PX: f32 =
@@ -14,3 +15,7 @@ pub const RENDER_CHUNK_SIZE: UVec2 = UVec2 {
     y: CHUNK_SIZE.y * 2,
 };
 pub const CHUNK_SPAWNING_CHANNEL_BUFFER_SIZE: usize = 1024;
+
+// Path costs, diagonal steps being about √2 times longer
+pub const PATH_STRAIGHT_COST: u32 = 10;
+pub const PATH_DIAGONAL_COST: u32 = 14;
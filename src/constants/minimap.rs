@@ -0,0 +1,31 @@
+// MAP COLORS
+// RGBA colors of each relief level, from the water up
+pub const MAP_LEVEL_COLORS: [[u8; 4]; 8] = [
+    [64, 112, 200, 255],
+    [206, 180, 120, 255],
+    [110, 180, 80, 255],
+    [96, 166, 70, 255],
+    [84, 152, 62, 255],
+    [52, 110, 50, 255],
+    [44, 98, 44, 255],
+    [36, 86, 38, 255],
+];
+pub const MAP_UNEXPLORED_COLOR: [u8; 4] = [0, 0, 0, 200];
+pub const MAP_USER_COLOR: [u8; 4] = [255, 255, 255, 255];
+pub const MAP_BOT_COLOR: [u8; 4] = [80, 220, 255, 255];
+pub const MAP_PLACE_COLOR: [u8; 4] = [255, 210, 40, 255];
+// Half the size of the markers
+pub const MAP_MARKER_RADIUS: i32 = 1; // unit: map pixels
+
+// MINIMAP
+// One map pixel per tile, centered on the user
+pub const MINIMAP_SIZE: u32 = 96; // unit: tiles
+pub const MINIMAP_SCALE: f32 = 2.;
+pub const MINIMAP_MARGIN: f32 = 8.; // unit: pixels
+
+// WORLD MAP
+pub const WORLD_MAP_WIDTH: u32 = 320; // unit: tiles
+pub const WORLD_MAP_HEIGHT: u32 = 192; // unit: tiles
+
+// Part of the window height covered by the world map
+pub const WORLD_MAP_HEIGHT_PERCENT: f32 = 90.;
@@ -6,13 +6,35 @@ pub mod action;
 pub mod bot;
 // Hardcoded character values
 pub mod character;
+// Hardcoded day and night cycle
+pub mod clock;
 // Hardcoded display configuration
 pub mod display;
+// Hardcoded exploration and its save
+pub mod exploration;
 // Hardcoded generation parameters
 pub mod generation;
+// Hardcoded input configuration
+pub mod input;
+// Hardcoded interactable objects
+pub mod interaction;
+// Hardcoded items
+pub mod item;
 // Hardcoded chunk management
 pub mod map;
+// Hardcoded map colors and sizes
+pub mod minimap;
+// Hardcoded quest limits
+pub mod quest;
 // Hardcoded sprites
 pub mod sprites;
+// Hardcoded structure templates
+pub mod structure;
 // Hardcoded tileset
 pub mod tileset;
+// Hardcoded tick rates
+pub mod tps;
+// Hardcoded weathers
+pub mod weather;
+// Hardcoded wildlife creatures
+pub mod wildlife;
@@ -0,0 +1,16 @@
+// PROPOSALS
+// Quests the user can have at once
+pub const QUEST_MAX_ACTIVE: usize = 3;
+pub const QUEST_MAX_OBJECTIVES: usize = 4;
+// Maximum number of items of a collect objective
+pub const QUEST_MAX_ITEMS: u32 = 20;
+// Maximum distance between the giver and a tile to reach
+pub const QUEST_MAX_DISTANCE: i32 = 32; // unit: tiles
+
+// COMPLETION
+// Distance from which a tile is reached
+pub const QUEST_REACH_DISTANCE: i32 = 1; // unit: tiles
+
+// Said by the giver of a quest
+pub const QUEST_PROPOSED_TEXT: &str = "I have a quest for you:";
+pub const QUEST_COMPLETED_TEXT: &str = "Well done, you completed";
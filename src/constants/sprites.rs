@@ -13,6 +13,7 @@ pub const BUBBLE_FPS: f64 = 8.;
 pub const BUBBLE_RELATIVE_POSITION: Vec3 = Vec3::new(0., TILE, 10.);
 
 pub const TYPE_EFFECT_SPRITE: &str = "effect/bubble.png";
+pub const TYPE_EFFECT_DURATION: f32 = 2.; // unit: seconds
 
 pub static TYPE_EFFECT: Lazy<DefinedAnimation> =
     Lazy::new(|| DefinedAnimation(new_animation(0..=19, BUBBLE_FPS).repeat_from(13)));
@@ -32,14 +33,22 @@ pub const PLAYER_SPRITE_LAYER: f32 = 4.;
 pub const PLAYER_SPRITE_GRID: AnimationSpriteGrid = AnimationSpriteGrid {
     size: Vec2::new(48., 48.),
     columns: 8,
-    rows: 24,
+    rows: 28,
     padding: None,
     offset: None,
 };
 
+// Height of the arc followed when going up or down a ramp
+pub const CLIMB_HEIGHT: f32 = TILE / 2.;
+
 pub const PLAYER_STAND_FPS: f64 = 5.;
 pub const PLAYER_WALK_FPS: f64 = 15.;
 pub const PLAYER_RUN_FPS: f64 = 15.;
+// The last rows of the sheet are the walk cycle cut at the waterline
+pub const PLAYER_SWIM_FPS: f64 = 6.;
+// The tool swing of the sheet is played as an attack
+pub const PLAYER_ATTACK_FPS: f64 = 20.;
+pub const PLAYER_BLOCK_FPS: f64 = 5.;
 
 pub static PLAYER_SPRITE_INDICES_MAP: Lazy<ActionAnimationMap> = Lazy::new(|| {
     ActionAnimationMap(HashMap::from([
@@ -94,10 +103,142 @@ pub static PLAYER_SPRITE_INDICES_MAP: Lazy<ActionAnimationMap> = Lazy::new(|| {
             Action::new(ActionKind::Run, ActionDirection::Left),
             new_animation(88..96, PLAYER_RUN_FPS),
         ),
+        // Swim Actions
+        (
+            Action::new(ActionKind::Swim, ActionDirection::Down),
+            new_animation(192..200, PLAYER_SWIM_FPS),
+        ),
+        (
+            Action::new(ActionKind::Swim, ActionDirection::Up),
+            new_animation(200..208, PLAYER_SWIM_FPS),
+        ),
+        (
+            Action::new(ActionKind::Swim, ActionDirection::Left),
+            new_animation(208..216, PLAYER_SWIM_FPS),
+        ),
+        (
+            Action::new(ActionKind::Swim, ActionDirection::Right),
+            new_animation(216..224, PLAYER_SWIM_FPS),
+        ),
         // Type Actions
         (
             Action::new(ActionKind::Type, ActionDirection::Down),
             new_animation(0..8, PLAYER_STAND_FPS),
         ),
+        // Attack Actions
+        (
+            Action::new(ActionKind::Attack, ActionDirection::Down),
+            new_animation(128..136, PLAYER_ATTACK_FPS),
+        ),
+        (
+            Action::new(ActionKind::Attack, ActionDirection::Up),
+            new_animation(136..144, PLAYER_ATTACK_FPS),
+        ),
+        (
+            Action::new(ActionKind::Attack, ActionDirection::Left),
+            new_animation(144..152, PLAYER_ATTACK_FPS),
+        ),
+        (
+            Action::new(ActionKind::Attack, ActionDirection::Right),
+            new_animation(152..160, PLAYER_ATTACK_FPS),
+        ),
+        // Block Actions, holding the tool up
+        (
+            Action::new(ActionKind::Block, ActionDirection::Down),
+            new_animation([97], PLAYER_BLOCK_FPS),
+        ),
+        (
+            Action::new(ActionKind::Block, ActionDirection::Up),
+            new_animation([105], PLAYER_BLOCK_FPS),
+        ),
+        (
+            Action::new(ActionKind::Block, ActionDirection::Left),
+            new_animation([113], PLAYER_BLOCK_FPS),
+        ),
+        (
+            Action::new(ActionKind::Block, ActionDirection::Right),
+            new_animation([121], PLAYER_BLOCK_FPS),
+        ),
+        // Knockback Actions, facing the attacker
+        (
+            Action::new(ActionKind::Knockback, ActionDirection::Down),
+            new_animation([8], PLAYER_STAND_FPS),
+        ),
+        (
+            Action::new(ActionKind::Knockback, ActionDirection::Up),
+            new_animation([0], PLAYER_STAND_FPS),
+        ),
+        (
+            Action::new(ActionKind::Knockback, ActionDirection::Left),
+            new_animation([24], PLAYER_STAND_FPS),
+        ),
+        (
+            Action::new(ActionKind::Knockback, ActionDirection::Right),
+            new_animation([16], PLAYER_STAND_FPS),
+        ),
     ]))
 });
+
+// CREATURES
+pub const RABBIT_SPRITE: &str = "character/rabbit.png";
+pub const CRAB_SPRITE: &str = "character/crab.png";
+pub const WOLF_SPRITE: &str = "character/wolf.png";
+pub const CREATURE_SPRITE_LAYER: f32 = PLAYER_SPRITE_LAYER;
+
+// One row per direction (down, up, left, right), standing then stepping
+pub const CREATURE_SPRITE_GRID: AnimationSpriteGrid = AnimationSpriteGrid {
+    size: Vec2::new(16., 16.),
+    columns: 2,
+    rows: 4,
+    padding: None,
+    offset: None,
+};
+
+pub static RABBIT_SPRITE_INDICES_MAP: Lazy<ActionAnimationMap> =
+    Lazy::new(|| creature_animations(2., 8., 12.));
+pub static CRAB_SPRITE_INDICES_MAP: Lazy<ActionAnimationMap> =
+    Lazy::new(|| creature_animations(1., 6., 10.));
+pub static WOLF_SPRITE_INDICES_MAP: Lazy<ActionAnimationMap> =
+    Lazy::new(|| creature_animations(2., 6., 10.));
+
+/// Builds the animations of a creature sheet laid out like `CREATURE_SPRITE_GRID`.
+///
+/// Attacks quickly alternate both frames, blocks and knockbacks hold the standing
+/// frame, the latter facing the attacker.
+fn creature_animations(stand_fps: f64, walk_fps: f64, run_fps: f64) -> ActionAnimationMap {
+    let rows = [
+        ActionDirection::Down,
+        ActionDirection::Up,
+        ActionDirection::Left,
+        ActionDirection::Right,
+    ];
+    let columns = CREATURE_SPRITE_GRID.columns;
+    let mut map = HashMap::new();
+    for (row, direction) in rows.into_iter().enumerate() {
+        let start = row * columns;
+        map.insert(
+            Action::new(ActionKind::Stand, direction.clone()),
+            new_animation([start], stand_fps),
+        );
+        map.insert(
+            Action::new(ActionKind::Block, direction.clone()),
+            new_animation([start], stand_fps),
+        );
+        for (kind, fps) in [
+            (ActionKind::Walk, walk_fps),
+            (ActionKind::Run, run_fps),
+            (ActionKind::Swim, walk_fps),
+            (ActionKind::Attack, run_fps * 2.),
+        ] {
+            map.insert(
+                Action::new(kind, direction.clone()),
+                new_animation(start..start + columns, fps),
+            );
+        }
+        map.insert(
+            Action::new(ActionKind::Knockback, direction.opposite()),
+            new_animation([start], stand_fps),
+        );
+    }
+    ActionAnimationMap(map)
+}
@@ -0,0 +1,63 @@
+use bevy::prelude::*;
+
+use crate::components::structure::{StructureKind, StructureTemplate};
+
+use super::generation::WORLD_SEED;
+use super::map::{CHUNK_SIZE, TILE};
+
+// PLACEMENT
+// Seed of the structures of every region
+pub const STRUCTURE_SEED: u64 = WORLD_SEED.wrapping_add(2);
+// Regions hold at most one structure, fully inside them
+pub const REGION_SIZE: i32 = 32; // unit: tiles
+pub const STRUCTURE_CHANCE: f64 = 0.6;
+// Regions are made of whole chunks
+const _: () =
+    assert!(REGION_SIZE % CHUNK_SIZE.x as i32 == 0 && REGION_SIZE % CHUNK_SIZE.y as i32 == 0);
+// Structures keep away from the spawn and its objects
+pub const STRUCTURE_SPAWN_CLEARANCE: i32 = 8; // unit: tiles
+
+// All the structures, placed on flat and dry land
+pub static STRUCTURES: [StructureTemplate; 4] = [
+    StructureTemplate {
+        kind: StructureKind::Ruins,
+        weight: 3,
+        names: &["Old Ruins", "Mossy Ruins", "Fallen Keep", "Broken Tower"],
+        rows: &["#.#.#", ".....", "#...#", "##.##"],
+    },
+    StructureTemplate {
+        kind: StructureKind::Campfire,
+        weight: 3,
+        names: &["Hunter's Camp", "Quiet Camp", "Traveler's Rest"],
+        rows: &["...", ".F.", "..."],
+    },
+    StructureTemplate {
+        kind: StructureKind::Village,
+        weight: 1,
+        names: &["Oakvale", "Mossbrook", "Pebbleton", "Fernhill"],
+        rows: &[
+            "HH...HH", "HH...HH", ".......", "...F..S", "HH.....", "HH.....",
+        ],
+    },
+    StructureTemplate {
+        kind: StructureKind::Signpost,
+        weight: 2,
+        names: &["Crossroads", "Old Signpost"],
+        rows: &[" . ", ".S.", " . "],
+    },
+];
+
+// PIECES
+// The tileset has no structures, they are drawn as plain sprites
+pub const WALL_SIZE: Vec2 = Vec2::new(TILE, TILE);
+pub const WALL_COLOR: Color = Color::rgb(0.5, 0.5, 0.55);
+pub const HOUSE_SIZE: Vec2 = Vec2::new(TILE, TILE);
+pub const HOUSE_COLOR: Color = Color::rgb(0.55, 0.3, 0.2);
+pub const CAMPFIRE_SIZE: Vec2 = Vec2::new(TILE * 0.5, TILE * 0.5);
+pub const CAMPFIRE_COLOR: Color = Color::rgb(1., 0.5, 0.1);
+// Between the tilemap layers and the characters
+pub const STRUCTURE_LAYER: f32 = 3.;
+
+// SIGNS
+pub const SIGN_WELCOME_TEXT: &str = "Welcome to";
+pub const SIGN_NOTHING_TEXT: &str = "Nothing around here.";
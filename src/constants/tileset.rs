@@ -95,6 +95,17 @@ pub const TEXTURE_ID_OFFSET_MAP: TextureIDOffsetPHF = TextureIDOffsetPHF(phf_map
     7u32 => TILESET_SIZE*DARKER_GRASS_HILL,
 });
 
+// Stepping stones drawn on ramps, by relief level
+pub static TEXTURE_RAMP_IDS_MAP: phf::Map<u32, u32> = phf_map! {
+    1u32 => 466,
+    2u32 => 466,
+    3u32 => 466,
+    4u32 => 466,
+    5u32 => 499,
+    6u32 => 499,
+    7u32 => 499,
+};
+
 const WATER_FPS: f32 = 1.;
 
 // All texture animations
@@ -0,0 +1,100 @@
+use bevy::prelude::*;
+
+use crate::components::weather::{
+    ParticleDefinition, WeatherDefinition, WeatherKind, WeatherSoundDefinition,
+};
+use crate::components::wildlife::Biome;
+use crate::constants::generation::WORLD_SEED;
+
+// Seed of the weather of every period
+pub const WEATHER_SEED: u64 = WORLD_SEED.wrapping_add(3);
+// In-game duration between two weather changes
+pub const WEATHER_PERIOD: f32 = 3.; // unit: hours
+
+// Particles spawned per frame, until the weather count is reached
+pub const WEATHER_PARTICLE_SPAWN_RATE: usize = 4;
+
+// All the weathers, in the order of `WeatherKind`
+pub static WEATHERS: [WeatherDefinition; 5] = [
+    WeatherDefinition {
+        biomes: &[Biome::Shore, Biome::Meadow, Biome::Forest],
+        transitions: &[
+            (WeatherKind::Clear, 6),
+            (WeatherKind::Rain, 2),
+            (WeatherKind::Fog, 1),
+            (WeatherKind::Snow, 1),
+        ],
+        remark: "What a nice weather!",
+        movement_factor: 1.,
+        overlay: Color::NONE,
+        particles: None,
+        sound: None,
+    },
+    WeatherDefinition {
+        biomes: &[Biome::Shore, Biome::Meadow, Biome::Forest],
+        transitions: &[
+            (WeatherKind::Clear, 3),
+            (WeatherKind::Rain, 2),
+            (WeatherKind::Storm, 1),
+        ],
+        remark: "Here comes the rain.",
+        movement_factor: 1.,
+        overlay: Color::rgba(0.2, 0.25, 0.35, 0.15),
+        particles: Some(ParticleDefinition {
+            count: 80,
+            size: Vec2::new(1., 6.),
+            velocity: Vec2::new(-10., 120.),
+            drift: 2.,
+            color: Color::rgba(0.6, 0.7, 1., 0.6),
+        }),
+        sound: Some(WeatherSoundDefinition {
+            path: "sound/rain.wav",
+            volume: 0.4,
+        }),
+    },
+    WeatherDefinition {
+        biomes: &[Biome::Shore, Biome::Meadow],
+        transitions: &[(WeatherKind::Rain, 2), (WeatherKind::Storm, 1)],
+        remark: "A storm! Let's stay careful.",
+        movement_factor: 0.75,
+        overlay: Color::rgba(0.1, 0.1, 0.2, 0.3),
+        particles: Some(ParticleDefinition {
+            count: 160,
+            size: Vec2::new(1., 8.),
+            velocity: Vec2::new(-30., 180.),
+            drift: 5.,
+            color: Color::rgba(0.6, 0.7, 1., 0.7),
+        }),
+        sound: Some(WeatherSoundDefinition {
+            path: "sound/storm.wav",
+            volume: 0.6,
+        }),
+    },
+    WeatherDefinition {
+        biomes: &[Biome::Shore, Biome::Forest],
+        transitions: &[(WeatherKind::Clear, 2), (WeatherKind::Fog, 1)],
+        remark: "I can barely see anything in this fog.",
+        movement_factor: 1.,
+        overlay: Color::rgba(0.85, 0.85, 0.9, 0.45),
+        particles: None,
+        sound: None,
+    },
+    WeatherDefinition {
+        biomes: &[Biome::Forest],
+        transitions: &[(WeatherKind::Clear, 2), (WeatherKind::Snow, 2)],
+        remark: "Look, it's snowing!",
+        movement_factor: 0.85,
+        overlay: Color::rgba(0.9, 0.95, 1., 0.15),
+        particles: Some(ParticleDefinition {
+            count: 60,
+            size: Vec2::new(2., 2.),
+            velocity: Vec2::new(0., 15.),
+            drift: 6.,
+            color: Color::rgba(1., 1., 1., 0.8),
+        }),
+        sound: Some(WeatherSoundDefinition {
+            path: "sound/wind.wav",
+            volume: 0.3,
+        }),
+    },
+];
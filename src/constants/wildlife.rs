@@ -0,0 +1,62 @@
+use crate::components::wildlife::{Biome, CreatureDefinition, Temperament};
+use crate::constants::generation::WORLD_SEED;
+use crate::constants::sprites::*;
+
+// SPAWNING
+// Seed of the creatures of every chunk
+pub const WILDLIFE_SEED: u64 = WORLD_SEED.wrapping_add(1);
+// Each attempt picks a random tile of the chunk
+pub const WILDLIFE_SPAWN_ATTEMPTS: u32 = 3;
+pub const WILDLIFE_SPAWN_CHANCE: f64 = 0.3;
+
+// BEHAVIOR
+pub const WILDLIFE_THINK_INTERVAL: f32 = 0.4; // unit: seconds
+pub const WILDLIFE_WANDER_CHANCE: f64 = 0.3;
+// Distance from the spawn point beyond which creatures wander back
+pub const WILDLIFE_ROAM_DISTANCE: i32 = 6; // unit: tiles
+
+// Distance from the spawn point beyond which creatures give up a chase
+pub const WILDLIFE_CHASE_DISTANCE: i32 = 12; // unit: tiles
+
+// All the creatures living in the wild
+pub static CREATURES: [CreatureDefinition; 3] = [
+    CreatureDefinition {
+        name: "Rabbit",
+        sprite: RABBIT_SPRITE,
+        sprite_grid: CREATURE_SPRITE_GRID,
+        animations: &RABBIT_SPRITE_INDICES_MAP,
+        biomes: &[Biome::Meadow],
+        weight: 3,
+        health: 10,
+        temperament: Temperament::Passive,
+        sight: 4,
+        damage: 0,
+        attack_cooldown: 0.,
+    },
+    CreatureDefinition {
+        name: "Crab",
+        sprite: CRAB_SPRITE,
+        sprite_grid: CREATURE_SPRITE_GRID,
+        animations: &CRAB_SPRITE_INDICES_MAP,
+        biomes: &[Biome::Shore],
+        weight: 1,
+        health: 15,
+        temperament: Temperament::Passive,
+        sight: 2,
+        damage: 0,
+        attack_cooldown: 0.,
+    },
+    CreatureDefinition {
+        name: "Wolf",
+        sprite: WOLF_SPRITE,
+        sprite_grid: CREATURE_SPRITE_GRID,
+        animations: &WOLF_SPRITE_INDICES_MAP,
+        biomes: &[Biome::Forest],
+        weight: 1,
+        health: 30,
+        temperament: Temperament::Hostile,
+        sight: 5,
+        damage: 10,
+        attack_cooldown: 1.5,
+    },
+];
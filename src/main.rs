@@ -1,12 +1,22 @@
 use bevy::asset::AssetMetaCheck;
 use bevy::log::LogPlugin;
 use bevy::prelude::*;
-use bevy::time::common_conditions::on_timer;
 use bevy_ecs_tilemap::prelude::*;
 use bevy_pixel_camera::PixelCameraPlugin;
-use components::map::{ChunkMap, ChunkSpawningChannel, MainTilemapTexture};
-use constants::action::ACTION_TICK_FREQUENCY;
+use components::character::HealthChangeEvent;
+use components::clock::WorldClock;
+use components::exploration::ExploredAutosave;
+use components::gpt::{BotDialogues, BotSpeechEvent, GPTRequestLimiter};
+use components::interaction::InteractionEvent;
+use components::item::ItemActionEvent;
+use components::map::{ChunkMap, ChunkSpawningChannel, MainTilemapTexture, Occupancy};
+use components::quest::{QuestLog, QuestProposalEvent};
+use components::structure::{ChunkStructures, PointsOfInterest};
+use components::weather::{Weather, WeatherChangeEvent};
+use components::wildlife::ChunkCreatures;
+use constants::bot::BOT_MAX_CONCURRENT_REQUESTS;
 use constants::map::RENDER_CHUNK_SIZE;
+use constants::tps::MOVEMENT_TPS;
 use dotenv::dotenv;
 
 mod bundles;
@@ -45,23 +55,214 @@ fn main() {
         .insert_resource(MainTilemapTexture::default())
         .insert_resource(ChunkMap::new())
         .insert_resource(ChunkSpawningChannel::new())
+        .insert_resource(Occupancy::default())
+        .insert_resource(ChunkCreatures::default())
+        .insert_resource(ChunkStructures::default())
+        .insert_resource(PointsOfInterest::default())
+        .insert_resource(ExploredAutosave::default())
+        .insert_resource(WorldClock::default())
+        .insert_resource(Weather::default())
+        .insert_resource(QuestLog::default())
+        .insert_resource(Time::<Fixed>::from_duration(MOVEMENT_TPS))
+        .insert_resource(GPTRequestLimiter::new(BOT_MAX_CONCURRENT_REQUESTS))
+        .insert_resource(BotDialogues::default())
+        .add_event::<BotSpeechEvent>()
+        .add_event::<InteractionEvent>()
+        .add_event::<ItemActionEvent>()
+        .add_event::<HealthChangeEvent>()
+        .add_event::<WeatherChangeEvent>()
+        .add_event::<QuestProposalEvent>()
         .add_plugins(TilemapPlugin)
         .add_systems(Startup, systems::setup::setup)
+        .add_systems(Startup, systems::input::load_input_bindings)
+        .add_systems(Startup, systems::exploration::load_explored)
+        .add_systems(
+            Update,
+            systems::input::update_action_input
+                .before(systems::input::handle_input)
+                .before(systems::input::handle_free_input)
+                .before(systems::input::toggle_movement_mode),
+        )
+        .add_systems(
+            Update,
+            systems::movement::update_occupancy
+                .before(systems::input::handle_input)
+                .before(systems::input::handle_bot_input),
+        )
         .add_systems(Update, systems::input::handle_input)
+        .add_systems(
+            Update,
+            systems::input::handle_click_input
+                .after(systems::movement::update_occupancy)
+                .before(systems::input::handle_input),
+        )
+        .add_systems(
+            Update,
+            systems::input::toggle_movement_mode.before(systems::input::handle_input),
+        )
+        .add_systems(
+            Update,
+            systems::input::handle_free_input
+                .after(systems::movement::update_occupancy)
+                .after(systems::input::handle_input),
+        )
         .add_systems(Update, systems::input::handle_bot_input)
         .add_systems(Update, systems::chunk::create_chunk_tasks)
         .add_systems(Update, systems::chunk::fetch_chunk_tasks)
         .add_systems(Update, systems::chunk::handle_chunk_despawning)
+        .add_systems(
+            Update,
+            systems::structure::spawn_structures.before(systems::movement::update_occupancy),
+        )
+        .add_systems(
+            Update,
+            systems::structure::despawn_structures.after(systems::chunk::handle_chunk_despawning),
+        )
+        .add_systems(
+            Update,
+            systems::wildlife::spawn_wildlife.after(systems::movement::update_occupancy),
+        )
+        .add_systems(
+            Update,
+            systems::wildlife::despawn_wildlife.after(systems::chunk::handle_chunk_despawning),
+        )
+        .add_systems(
+            Update,
+            systems::wildlife::run_creatures.after(systems::combat::apply_knockback),
+        )
+        .add_systems(
+            Update,
+            systems::combat::apply_knockback
+                .after(systems::movement::update_occupancy)
+                .before(systems::input::handle_input)
+                .before(systems::input::handle_bot_input),
+        )
+        .add_systems(
+            Update,
+            systems::combat::resolve_hitboxes
+                .after(systems::movement::update_occupancy)
+                .before(systems::health::apply_health_changes),
+        )
         .add_systems(Update, systems::animation::animate_action_sprite)
         .add_systems(Update, systems::animation::animate_defined_sprite)
+        .add_systems(Update, systems::animation::animate_climb)
         .add_systems(Update, systems::bot::query_bot)
+        .add_systems(Update, systems::bot::run_offline_brain)
+        .add_systems(
+            Update,
+            systems::bot::react_to_weather.after(systems::weather::change_weather),
+        )
+        .add_systems(
+            Update,
+            systems::bot::follow_target.after(systems::input::handle_bot_input),
+        )
+        .add_systems(
+            Update,
+            systems::bot::go_to_destination.after(systems::input::handle_bot_input),
+        )
+        .add_systems(
+            Update,
+            systems::bot::attack_target.after(systems::input::handle_bot_input),
+        )
+        .add_systems(
+            Update,
+            systems::interaction::handle_interactions
+                .after(systems::input::handle_input)
+                .after(systems::input::handle_bot_input)
+                .before(systems::bot::deliver_bot_speech),
+        )
+        .add_systems(
+            Update,
+            systems::item::handle_item_actions
+                .after(systems::input::handle_input)
+                .after(systems::input::handle_bot_input)
+                .before(systems::health::apply_health_changes),
+        )
+        .add_systems(
+            Update,
+            systems::bot::deliver_bot_speech.after(systems::input::handle_bot_input),
+        )
+        .add_systems(Update, systems::display::despawn_expired)
+        .add_systems(Update, systems::exploration::record_explored_chunks)
+        .add_systems(
+            Update,
+            systems::exploration::explore_tiles.after(systems::exploration::record_explored_chunks),
+        )
+        .add_systems(
+            Update,
+            systems::exploration::darken_unexplored_tiles
+                .after(systems::exploration::explore_tiles),
+        )
+        // The exit event is sent in PostUpdate, right before the game quits
+        .add_systems(Last, systems::exploration::save_explored)
+        .add_systems(
+            Update,
+            systems::minimap::update_maps
+                .after(systems::input::update_action_input)
+                .after(systems::exploration::explore_tiles)
+                .after(systems::structure::spawn_structures),
+        )
+        .add_systems(
+            Update,
+            systems::quest::validate_quests.after(systems::input::handle_bot_input),
+        )
+        .add_systems(
+            Update,
+            systems::quest::track_quests
+                .after(systems::quest::validate_quests)
+                .after(systems::input::handle_input)
+                .after(systems::item::handle_item_actions)
+                .after(systems::interaction::handle_interactions),
+        )
+        .add_systems(
+            Update,
+            systems::quest::update_quest_log
+                .after(systems::input::update_action_input)
+                .after(systems::quest::track_quests),
+        )
+        .add_systems(Update, systems::clock::advance_clock)
+        .add_systems(
+            Update,
+            systems::clock::update_daylight.after(systems::clock::advance_clock),
+        )
+        .add_systems(
+            Update,
+            systems::weather::change_weather.after(systems::clock::advance_clock),
+        )
+        .add_systems(
+            Update,
+            systems::weather::update_weather_effects.after(systems::weather::change_weather),
+        )
+        .add_systems(
+            Update,
+            systems::weather::play_weather_sound.after(systems::weather::change_weather),
+        )
+        .add_systems(
+            Update,
+            (
+                systems::health::apply_fall_damage,
+                systems::health::apply_drowning,
+            )
+                .before(systems::health::apply_health_changes),
+        )
+        .add_systems(Update, systems::health::apply_health_changes)
+        .add_systems(Update, systems::health::update_invulnerability)
+        .add_systems(Update, systems::health::respawn)
+        .add_systems(
+            Update,
+            systems::health::update_health_bar.after(systems::health::apply_health_changes),
+        )
+        .add_systems(FixedUpdate, systems::movement::move_characters)
+        .add_systems(Update, systems::movement::interpolate_motion)
         .add_systems(
             Update,
-            systems::movement::move_characters.run_if(on_timer(ACTION_TICK_FREQUENCY)),
+            systems::movement::move_free_characters.after(systems::input::handle_free_input),
         )
         .add_systems(
             Update,
-            systems::movement::camera_follow_player.after(systems::movement::move_characters),
+            systems::movement::camera_follow_player
+                .after(systems::movement::interpolate_motion)
+                .after(systems::movement::move_free_characters),
         )
         .run();
 }
@@ -1,7 +1,27 @@
+use std::sync::atomic::Ordering;
+
 use bevy::prelude::*;
+use bevy::sprite::Anchor;
 
 use crate::components::action::*;
 use crate::components::animation::*;
+use crate::components::character::Busy;
+use crate::components::map::Terrain;
+use crate::components::texture::TilesetOffset;
+use crate::constants::sprites::CLIMB_HEIGHT;
+use crate::util::position::action_start_tile_pos;
+
+// Define a type for climbing character queries
+type ClimbingCharacterQuery<'a> = (
+    &'a mut TextureAtlasSprite,
+    &'a Handle<TextureAtlas>,
+    &'a Transform,
+    &'a TilesetOffset,
+    &'a Busy,
+    &'a Action,
+    &'a ActionTimer,
+    &'a Motion,
+);
 
 /// Updates the sprite animation based on the current action.
 ///
@@ -28,6 +48,53 @@ pub fn animate_action_sprite(
     }
 }
 
+/// Lifts the sprite of characters going up or down a ramp.
+///
+/// The sprite follows an arc while the action moves between two relief levels,
+/// without touching the transform so tile positions are unaffected.
+/// The anchor is relative to the height of each character's own sprite.
+///
+/// # Parameters
+/// - `terrain`: Relief levels of the loaded tiles.
+/// - `texture_atlases`: The texture atlas assets, giving the sprite sizes.
+/// - `query`: Query for accessing sprites and the current action of characters.
+pub fn animate_climb(
+    terrain: Terrain,
+    texture_atlases: Res<Assets<TextureAtlas>>,
+    mut query: Query<ClimbingCharacterQuery>,
+) {
+    for (mut sprite, atlas, transform, offset, busy, action, timer, motion) in query.iter_mut() {
+        let transformation = action.get_transformation();
+        if !busy.load(Ordering::Acquire) || transformation == Vec3::ZERO {
+            if !matches!(sprite.anchor, Anchor::Center) {
+                sprite.anchor = Anchor::Center;
+            }
+            continue;
+        }
+
+        let progress = timer.percent();
+        let start_pos = action_start_tile_pos(transform, offset, motion, timer);
+        let target_pos = start_pos + action.get_raw_transformation();
+
+        let is_climbing = matches!(
+            (terrain.level(&start_pos), terrain.level(&target_pos)),
+            (Some(level0), Some(level1)) if level0 != level1
+        );
+        let sprite_height = texture_atlases
+            .get(atlas)
+            .and_then(|atlas| atlas.textures.get(sprite.index))
+            .map(|rect| rect.height())
+            .filter(|height| *height > 0.);
+        sprite.anchor = match sprite_height {
+            Some(sprite_height) if is_climbing => {
+                let height = CLIMB_HEIGHT * (progress * std::f32::consts::PI).sin();
+                Anchor::Custom(Vec2::new(0., -height / sprite_height))
+            }
+            _ => Anchor::Center,
+        };
+    }
+}
+
 pub fn animate_defined_sprite(
     time: Res<Time>,
     mut query: Query<(
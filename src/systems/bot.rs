@@ -1,71 +1,806 @@
+use std::sync::atomic::Ordering;
+
+use bevy::log;
 use bevy::prelude::*;
+use strum::IntoEnumIterator;
 
+use crate::components::action::{
+    Action, ActionDirection, ActionDurationPHF, ActionKind, ActionTimer,
+};
+use crate::components::brain::{BotPerception, OfflineBrain};
 use crate::components::character::*;
-use crate::components::gpt::GPTAgent;
+use crate::components::clock::WorldClock;
+use crate::components::combat::Hitbox;
+use crate::components::command::{
+    ActionQueue, AttackTarget, Destination, FollowTarget, PathBackoff,
+};
+use crate::components::display::SpeechBubble;
+use crate::components::exploration::ExploredMap;
+use crate::components::gpt::{BotDialogues, BotSpeechEvent, GPTAgent, GPTRequestLimiter};
+use crate::components::item::{Inventory, ItemDrop};
+use crate::components::map::{Occupancy, Terrain};
+use crate::components::quest::QuestLog;
+use crate::components::structure::PointsOfInterest;
 use crate::components::texture::TilesetOffset;
-use crate::constants::bot::BOT_VIEW_DISTANCE;
+use crate::components::weather::{Weather, WeatherChangeEvent};
+use crate::components::wildlife::{Creature, Temperament};
+use crate::constants::bot::*;
+use crate::constants::character::PLAYER_ATTACK_DAMAGE;
+use crate::constants::exploration::{EXPLORE_HINT_DISTANCE, EXPLORE_HINT_STEP};
+use crate::constants::generation::WATER_LEVEL;
+use crate::constants::item::ITEM_REGISTRY;
+use crate::util::effect::spawn_speech;
 use crate::util::position::*;
 
-/// Processes bot behavior based on the environment and user positions.
+// Define a type for named character queries
+type NamedCharacterQuery<'a> = (Entity, &'a Name, &'a Transform, &'a TilesetOffset);
+
+// Define a type for offline bot queries
+type OfflineBotQuery<'a> = (
+    &'a Busy,
+    &'a Transform,
+    &'a TilesetOffset,
+    &'a ActionQueue,
+    &'a mut OfflineBrain,
+    Option<&'a GPTAgent>,
+);
+
+// Define a type for GPT bot queries
+type GptBotQuery<'a> = (
+    Entity,
+    &'a Transform,
+    &'a TilesetOffset,
+    &'a ActionQueue,
+    &'a mut GPTAgent,
+    Option<&'a FollowTarget>,
+    Option<&'a AttackTarget>,
+    Option<&'a BlockedBy>,
+    &'a Inventory,
+);
+
+// Define a type for bots walking along a path
+type PathingBotQuery<'a> = (
+    Entity,
+    &'a Busy,
+    &'a mut Action,
+    &'a mut ActionTimer,
+    &'a ActionDurationPHF,
+    &'a Transform,
+    &'a TilesetOffset,
+    &'a ActionQueue,
+    Option<&'a PathBackoff>,
+);
+
+/// Outcome of searching the next step toward a tile.
+enum PathStep {
+    /// The first step is reserved, with the cost of its destination.
+    Step(Action, f32),
+    /// Already close enough to the tile.
+    Arrived,
+    /// No path leads to the tile.
+    Unreachable,
+    /// The first step is occupied.
+    Blocked,
+}
+
+// Define a filter matching bots neither following, going somewhere nor attacking
+type IsFreeBot = (
+    With<IsBot>,
+    Without<FollowTarget>,
+    Without<Destination>,
+    Without<AttackTarget>,
+);
+
+// Define a filter matching users and bots
+type IsCharacter = Or<(With<IsUser>, With<IsBot>)>;
+
+/// Processes bot behavior based on the environment and character positions.
 ///
 /// This function checks the surroundings of each bot and updates its actions
-/// based on the relative position of the user. It leverages the GPTAgent to
-/// create actions for the bot based on the current game context.
+/// based on the relative position of the other characters. It leverages the
+/// GPTAgent to create commands for the bot based on the current game context.
+/// An unavailable model is only asked again after `BOT_RETRY_DELAY`.
+/// The bots take turns for the request slots, starting after the last one served.
 ///
 /// # Parameters
+/// - `time`: Resource containing time information.
+/// - `limiter`: Resource limiting the number of requests in flight.
+/// - `clock`: Resource keeping the in-game time.
+/// - `weather`: Resource holding the current weather.
+/// - `quest_log`: Resource holding the quests of the user.
+/// - `points_of_interest`: Resource registering the named structures.
+/// - `explored`: Resource remembering the explored tiles.
 /// - `bot_query`: Query to access bot characters and their properties.
-/// - `user_query`: Query to access user characters and their properties.
+/// - `character_query`: Query to access every named character and their properties.
+/// - `drop_query`: Query to access the items lying in the world.
+/// - `health_query`: Query to access the health of the bots.
+/// - `user_query`: Query to access the users health.
+/// - `creature_query`: Query to access the creatures around.
+/// - `last_served`: The last bot given a request slot.
+#[allow(clippy::too_many_arguments)]
 pub fn query_bot(
-    bot_query: Query<(&Transform, &TilesetOffset, &GPTAgent), With<IsBot>>,
-    user_query: Query<(&Transform, &TilesetOffset), With<IsUser>>,
+    time: Res<Time>,
+    limiter: Res<GPTRequestLimiter>,
+    clock: Res<WorldClock>,
+    weather: Res<Weather>,
+    quest_log: Res<QuestLog>,
+    points_of_interest: Res<PointsOfInterest>,
+    explored: Res<ExploredMap>,
+    mut bot_query: Query<GptBotQuery, With<IsBot>>,
+    character_query: Query<NamedCharacterQuery, IsCharacter>,
+    drop_query: Query<(&Transform, &ItemDrop)>,
+    health_query: Query<(&Health, &MaxHealth)>,
+    user_query: Query<(&Name, &Health, &MaxHealth, Has<Dead>), With<IsUser>>,
+    creature_query: Query<(&Transform, &TilesetOffset, &Creature)>,
+    mut last_served: Local<Option<Entity>>,
 ) {
-    for (transform, offset, agent) in bot_query.iter() {
-        let is_empty = {
-            if let Ok(queue) = agent.action_queue.try_read() {
-                queue.is_empty()
-            } else {
-                false
-            }
+    let mut bots = bot_query.iter().map(|(bot, ..)| bot).collect::<Vec<_>>();
+    bots.sort();
+    let start = bots.partition_point(|bot| Some(*bot) <= *last_served);
+    bots.rotate_left(start);
+
+    for bot in bots {
+        let Ok((
+            entity,
+            transform,
+            offset,
+            queue,
+            mut agent,
+            follow,
+            attack,
+            blocked_by,
+            inventory,
+        )) = bot_query.get_mut(bot)
+        else {
+            continue;
         };
+        if !queue.is_empty() || agent.is_busy() {
+            continue;
+        }
 
-        if !is_empty || agent.is_busy() {
-            return;
+        if !agent.is_available() && time.elapsed_seconds() < agent.retry_at {
+            continue;
         }
 
+        // Every request slot is taken, this bot goes first next frame
+        let Some(permit) = limiter.try_acquire() else {
+            return;
+        };
+        *last_served = Some(entity);
+        agent.retry_at = time.elapsed_seconds() + BOT_RETRY_DELAY;
+
         let bot_tile_pos = player_tile_pos(transform, offset);
 
         let mut map = String::from("Environment:\n");
+        map.push_str(&format!("Time of day: {}.\n", *clock));
+        map.push_str(&format!("Weather: {}.\n", weather.kind));
 
-        // Write user position
-        for (transform, offset) in user_query.iter() {
-            let user_tile_pos = player_tile_pos(transform, offset);
-            let relative_position = user_tile_pos - bot_tile_pos;
+        // Write other characters position
+        for (other, name, transform, offset) in character_query.iter() {
+            if other == entity {
+                continue;
+            }
+
+            let relative_position = player_tile_pos(transform, offset) - bot_tile_pos;
             if relative_position.x.abs() > BOT_VIEW_DISTANCE
                 || relative_position.y.abs() > BOT_VIEW_DISTANCE
             {
                 continue;
             }
 
-            let horizontal_direction = if relative_position.x > 0 {
-                "right"
-            } else {
-                "left"
+            map.push_str(&describe_relative_position(name, relative_position));
+        }
+
+        // Write the items lying around
+        for (transform, item_drop) in drop_query.iter() {
+            let relative_position =
+                pixel_pos_to_tile_pos(&transform.translation.xy()) - bot_tile_pos;
+            if relative_position.abs().max_element() > BOT_VIEW_DISTANCE {
+                continue;
+            }
+            map.push_str(&describe_relative_position(
+                &item_drop.describe(),
+                relative_position,
+            ));
+        }
+
+        // Write the creatures around, warning about the hostile ones
+        for (transform, offset, creature) in creature_query.iter() {
+            let relative_position = player_tile_pos(transform, offset) - bot_tile_pos;
+            if relative_position.abs().max_element() > BOT_VIEW_DISTANCE {
+                continue;
+            }
+            let name = match creature.species.temperament {
+                Temperament::Passive => creature.species.name.to_string(),
+                Temperament::Hostile => format!("{} (hostile)", creature.species.name),
             };
-            let vertical_direction = if relative_position.y > 0 {
-                "up"
+            map.push_str(&describe_relative_position(&name, relative_position));
+        }
+
+        // Write the known places around
+        for point in points_of_interest.values() {
+            let relative_position = point.center - bot_tile_pos;
+            if relative_position.abs().max_element() > BOT_PLACE_DISTANCE {
+                continue;
+            }
+            map.push_str(&format!(
+                "{} ({}): {}.\n",
+                point.name,
+                point.kind,
+                describe_offset(relative_position)
+            ));
+        }
+
+        // Write where the unexplored areas are
+        if let Some(direction) =
+            explored.unexplored_direction(bot_tile_pos, EXPLORE_HINT_DISTANCE, EXPLORE_HINT_STEP)
+        {
+            map.push_str(&format!(
+                "Unexplored areas are mostly to the {}.\n",
+                describe_direction(direction)
+            ));
+        }
+
+        // Write the carried items with their tags
+        let carried_items = inventory
+            .stacks()
+            .iter()
+            .map(|stack| match ITEM_REGISTRY.get(stack.id) {
+                Some(definition) if !definition.tags.is_empty() => {
+                    format!("{} ({})", stack.describe(), definition.tags.join(", "))
+                }
+                _ => stack.describe(),
+            })
+            .collect::<Vec<_>>();
+        if carried_items.is_empty() {
+            map.push_str("You carry nothing.\n");
+        } else {
+            map.push_str(&format!("You carry {}.\n", carried_items.join(", ")));
+        }
+
+        // Write the health of the bot and of the users
+        if let Ok((health, max_health)) = health_query.get(entity) {
+            map.push_str(&format!("Your health: {}/{}.\n", **health, **max_health));
+        }
+        for (name, health, max_health, dead) in user_query.iter() {
+            if dead {
+                map.push_str(&format!("{} is dead and will respawn soon.\n", name));
             } else {
-                "down"
-            };
+                map.push_str(&format!(
+                    "{} health: {}/{}.\n",
+                    name, **health, **max_health
+                ));
+            }
+        }
 
+        if let Some((_, name, _, _)) =
+            blocked_by.and_then(|blocker| character_query.get(**blocker).ok())
+        {
+            map.push_str(&format!("Your last move was blocked by {}.\n", name));
+        }
+
+        if let Some(follow) = follow {
             map.push_str(&format!(
-                "Player is {} to your {} and {} to your {}",
-                relative_position.x.abs(),
-                horizontal_direction,
-                relative_position.y.abs(),
-                vertical_direction
+                "You are following {} at {} tiles.\n",
+                follow.target, follow.distance
             ));
         }
 
-        agent.create_actions_with_extra_context(&map);
+        if let Some(attack) = attack {
+            map.push_str(&format!("You are attacking {}.\n", attack.target));
+        }
+
+        // Write the quests proposed by the bot, with their progress
+        for quest in quest_log.iter().filter(|quest| quest.giver == entity) {
+            let objectives = quest
+                .objectives
+                .iter()
+                .map(|objective| {
+                    let description = objective.objective.describe(bot_tile_pos);
+                    if objective.is_done {
+                        format!("{} (done)", description)
+                    } else {
+                        description
+                    }
+                })
+                .collect::<Vec<_>>();
+            map.push_str(&format!(
+                "Your quest \"{}\" is in progress: {}.\n",
+                quest.title,
+                objectives.join(", ")
+            ));
+        }
+
+        agent.create_commands_with_extra_context(&map, permit);
+    }
+}
+
+/// Lets the bots react to the weather changes.
+///
+/// Bots with an available model are told about the change, the others
+/// remark on the new weather.
+///
+/// # Parameters
+/// - `commands`: Commands for spawning speech bubbles.
+/// - `weather_events`: The weather changes.
+/// - `bot_query`: Query to access bots and their agents.
+pub fn react_to_weather(
+    mut commands: Commands,
+    mut weather_events: EventReader<WeatherChangeEvent>,
+    bot_query: Query<(Entity, Option<&GPTAgent>), With<IsBot>>,
+) {
+    for event in weather_events.read() {
+        for (entity, agent) in bot_query.iter() {
+            match agent {
+                Some(agent) if agent.is_available() => agent.add_context(&format!(
+                    "The weather changed from {} to {}.",
+                    event.previous, event.current
+                )),
+                _ => spawn_speech(
+                    &mut commands,
+                    entity,
+                    event.current.definition().remark.to_string(),
+                ),
+            }
+        }
+    }
+}
+
+/// Runs the offline brain of bots without an available model.
+///
+/// Each brain thinks at a fixed interval, only when its bot has nothing
+/// left to do, and fills the action queue with its decisions.
+///
+/// # Parameters
+/// - `time`: Resource containing time information.
+/// - `terrain`: Relief levels of the loaded tiles.
+/// - `bot_query`: Query to access bots and their brains.
+/// - `user_query`: Query to access the users positions.
+/// - `creature_query`: Query to access the creatures, finding threats.
+pub fn run_offline_brain(
+    time: Res<Time>,
+    terrain: Terrain,
+    mut bot_query: Query<OfflineBotQuery, IsFreeBot>,
+    user_query: Query<(&Transform, &TilesetOffset), With<IsUser>>,
+    creature_query: Query<(&Transform, &TilesetOffset, &Creature)>,
+) {
+    for (busy, transform, offset, queue, mut brain, agent) in bot_query.iter_mut() {
+        if !brain.tick(time.delta()) {
+            continue;
+        }
+        if busy.load(Ordering::Acquire)
+            || !queue.is_empty()
+            || agent.is_some_and(GPTAgent::is_available)
+        {
+            continue;
+        }
+
+        let bot_tile_pos = player_tile_pos(transform, offset);
+        let user = user_query
+            .iter()
+            .map(|(transform, offset)| player_tile_pos(transform, offset) - bot_tile_pos)
+            .filter(|relative_position| relative_position.abs().max_element() <= BOT_VIEW_DISTANCE)
+            .min_by_key(|relative_position| relative_position.abs().max_element());
+        let safe_directions = ActionDirection::iter()
+            .filter(|direction| {
+                let action = Action::new(ActionKind::Walk, direction.clone());
+                let target = bot_tile_pos + action.get_raw_transformation();
+                terrain.is_action_possible(&action, &bot_tile_pos)
+                    && terrain.level(&target) != Some(WATER_LEVEL)
+                    && terrain.fall_height(&bot_tile_pos, &target) == 0
+            })
+            .collect();
+
+        let threat = creature_query
+            .iter()
+            .filter(|(_, _, creature)| creature.species.temperament == Temperament::Hostile)
+            .map(|(transform, offset, _)| player_tile_pos(transform, offset) - bot_tile_pos)
+            .filter(|relative_position| {
+                let is_close = |position: IVec2| {
+                    (*relative_position - position).abs().max_element() <= BRAIN_DEFEND_DISTANCE
+                };
+                is_close(IVec2::ZERO) || user.is_some_and(is_close)
+            })
+            .min_by_key(|relative_position| relative_position.abs().max_element());
+
+        queue.extend(brain.think(&BotPerception {
+            user,
+            safe_directions,
+            threat,
+        }));
     }
 }
+
+/// Moves following bots toward their target.
+///
+/// Once the bot has nothing else to do, a path is searched with the terrain
+/// walkability rules and its first step is taken. The bot runs when far away
+/// and stands still when the target is close enough or out of sight.
+///
+/// # Parameters
+/// - `commands`: Commands for clearing the blocked status of moving bots.
+/// - `time`: Resource containing time information.
+/// - `terrain`: Relief levels of the loaded tiles.
+/// - `occupancy`: Tiles occupied by characters, avoided by the path.
+/// - `bot_query`: Query to access following bots and their actions.
+/// - `character_query`: Query to access every named character and their properties.
+pub fn follow_target(
+    mut commands: Commands,
+    time: Res<Time>,
+    terrain: Terrain,
+    mut occupancy: ResMut<Occupancy>,
+    mut bot_query: Query<(PathingBotQuery, &FollowTarget), With<IsBot>>,
+    character_query: Query<NamedCharacterQuery, IsCharacter>,
+) {
+    for (
+        (entity, busy, mut action, mut timer, duration, transform, offset, queue, backoff),
+        follow,
+    ) in bot_query.iter_mut()
+    {
+        if busy.load(Ordering::Acquire) || !queue.is_empty() {
+            continue;
+        }
+
+        let Some((_, _, target_transform, target_offset)) =
+            character_query.iter().find(|(other, name, _, _)| {
+                *other != entity && name.as_str().eq_ignore_ascii_case(&follow.target)
+            })
+        else {
+            continue;
+        };
+
+        let bot_tile_pos = player_tile_pos(transform, offset);
+        let target_tile_pos = player_tile_pos(target_transform, target_offset);
+        if is_backing_off(backoff, target_tile_pos, &time) {
+            continue;
+        }
+        let step = take_path_step(
+            &terrain,
+            &mut occupancy,
+            entity,
+            bot_tile_pos,
+            target_tile_pos,
+            follow.distance,
+        );
+        if let Some((next_action, terrain_cost)) =
+            handle_path_step(&mut commands, &time, entity, target_tile_pos, step)
+        {
+            *action = next_action;
+            *timer = duration.generate_timer(&action, terrain_cost);
+            busy.store(true, Ordering::Release);
+        }
+    }
+}
+
+/// Makes attacking bots chase and hit their target.
+///
+/// The closest living character or creature with the target name is chased
+/// like in `follow_target`, and attacked once adjacent. The attack target is
+/// dropped when no such target is in sight anymore.
+///
+/// # Parameters
+/// - `commands`: Commands for spawning hitboxes and removing finished attack targets.
+/// - `time`: Resource containing time information.
+/// - `terrain`: Relief levels of the loaded tiles.
+/// - `occupancy`: Tiles occupied by characters, avoided by the path.
+/// - `bot_query`: Query to access attacking bots and their actions.
+/// - `target_query`: Query to access every living named character and creature.
+pub fn attack_target(
+    mut commands: Commands,
+    time: Res<Time>,
+    terrain: Terrain,
+    mut occupancy: ResMut<Occupancy>,
+    mut bot_query: Query<(PathingBotQuery, &AttackTarget), With<IsBot>>,
+    target_query: Query<NamedCharacterQuery, (With<Health>, Without<Dead>)>,
+) {
+    for (
+        (entity, busy, mut action, mut timer, duration, transform, offset, queue, backoff),
+        attack,
+    ) in bot_query.iter_mut()
+    {
+        if busy.load(Ordering::Acquire) || !queue.is_empty() {
+            continue;
+        }
+
+        let bot_tile_pos = player_tile_pos(transform, offset);
+        let Some(target_tile_pos) = target_query
+            .iter()
+            .filter(|(other, name, _, _)| {
+                *other != entity && name.as_str().eq_ignore_ascii_case(&attack.target)
+            })
+            .map(|(_, _, transform, offset)| player_tile_pos(transform, offset))
+            .filter(|tile_pos| (*tile_pos - bot_tile_pos).abs().max_element() <= BOT_VIEW_DISTANCE)
+            .min_by_key(|tile_pos| (*tile_pos - bot_tile_pos).abs().max_element())
+        else {
+            commands.entity(entity).remove::<AttackTarget>();
+            continue;
+        };
+
+        let relative_position = target_tile_pos - bot_tile_pos;
+        if relative_position.abs().max_element() <= 1 {
+            let Some(direction) = ActionDirection::from_vector(relative_position) else {
+                continue;
+            };
+            *action = Action::new(ActionKind::Attack, direction);
+            commands.spawn(Hitbox::new(
+                entity,
+                &action,
+                &bot_tile_pos,
+                PLAYER_ATTACK_DAMAGE,
+                duration.hit_delay(),
+            ));
+            *timer = duration.generate_timer(&action, 1.);
+            busy.store(true, Ordering::Release);
+            continue;
+        }
+
+        if is_backing_off(backoff, target_tile_pos, &time) {
+            continue;
+        }
+        let step = take_path_step(
+            &terrain,
+            &mut occupancy,
+            entity,
+            bot_tile_pos,
+            target_tile_pos,
+            1,
+        );
+        if let Some((next_action, terrain_cost)) =
+            handle_path_step(&mut commands, &time, entity, target_tile_pos, step)
+        {
+            *action = next_action;
+            *timer = duration.generate_timer(&action, terrain_cost);
+            busy.store(true, Ordering::Release);
+        }
+    }
+}
+
+/// Moves bots toward their destination.
+///
+/// Works like `follow_target`, the destination is dropped once reached
+/// or if no path leads to it.
+///
+/// # Parameters
+/// - `commands`: Commands for removing reached destinations and blocked status.
+/// - `time`: Resource containing time information.
+/// - `terrain`: Relief levels of the loaded tiles.
+/// - `occupancy`: Tiles occupied by characters, avoided by the path.
+/// - `bot_query`: Query to access bots with a destination and their actions.
+pub fn go_to_destination(
+    mut commands: Commands,
+    time: Res<Time>,
+    terrain: Terrain,
+    mut occupancy: ResMut<Occupancy>,
+    mut bot_query: Query<(PathingBotQuery, &Destination), With<IsBot>>,
+) {
+    for (
+        (entity, busy, mut action, mut timer, duration, transform, offset, queue, backoff),
+        destination,
+    ) in bot_query.iter_mut()
+    {
+        if busy.load(Ordering::Acquire) || !queue.is_empty() {
+            continue;
+        }
+        if is_backing_off(backoff, **destination, &time) {
+            continue;
+        }
+
+        let bot_tile_pos = player_tile_pos(transform, offset);
+        let step = take_path_step(
+            &terrain,
+            &mut occupancy,
+            entity,
+            bot_tile_pos,
+            **destination,
+            0,
+        );
+        if matches!(step, PathStep::Arrived | PathStep::Unreachable) {
+            commands.entity(entity).remove::<Destination>();
+            continue;
+        }
+        if let Some((next_action, terrain_cost)) =
+            handle_path_step(&mut commands, &time, entity, **destination, step)
+        {
+            *action = next_action;
+            *timer = duration.generate_timer(&action, terrain_cost);
+            busy.store(true, Ordering::Release);
+        }
+    }
+}
+
+/// Delivers what bots say to the other characters.
+///
+/// Each message is displayed above the speaker and, when the listener is a bot,
+/// added to the listener's conversation. Dialogues between two bots are capped
+/// by `BotDialogues` to prevent endless conversations.
+///
+/// # Parameters
+/// - `commands`: Commands for spawning speech bubbles.
+/// - `time`: Resource containing time information.
+/// - `dialogues`: Resource tracking the length of dialogues.
+/// - `speech_events`: Messages said by bots.
+/// - `name_query`: Query to access character names and positions, listeners out of sight do not hear.
+/// - `agent_query`: Query to access bots and their agents.
+/// - `speech_query`: Query to access the speech bubbles already displayed.
+pub fn deliver_bot_speech(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut dialogues: ResMut<BotDialogues>,
+    mut speech_events: EventReader<BotSpeechEvent>,
+    name_query: Query<(&Name, &Transform, &TilesetOffset)>,
+    agent_query: Query<(Entity, &Name, &GPTAgent), With<IsBot>>,
+    speech_query: Query<(Entity, &Parent), With<SpeechBubble>>,
+) {
+    for event in speech_events.read() {
+        let Ok((speaker_name, speaker_transform, speaker_offset)) = name_query.get(event.speaker)
+        else {
+            continue;
+        };
+        log::info!(
+            "{} says to {}: \"{}\"",
+            speaker_name,
+            event.target,
+            event.message
+        );
+
+        // Replace the previous speech bubble
+        for (bubble, parent) in speech_query.iter() {
+            if parent.get() == event.speaker {
+                commands.entity(bubble).despawn_recursive();
+            }
+        }
+        spawn_speech(
+            &mut commands,
+            event.speaker,
+            format!("{}, {}", event.target, event.message),
+        );
+
+        let Some((listener, listener_name, listener_agent)) = agent_query
+            .iter()
+            .find(|(_, name, _)| name.as_str().eq_ignore_ascii_case(&event.target))
+        else {
+            continue;
+        };
+        if listener == event.speaker {
+            continue;
+        }
+        let speaker_agent = agent_query.get(event.speaker).ok();
+
+        let Ok((_, listener_transform, listener_offset)) = name_query.get(listener) else {
+            continue;
+        };
+        let distance = player_tile_pos(listener_transform, listener_offset)
+            - player_tile_pos(speaker_transform, speaker_offset);
+        if distance.abs().max_element() > BOT_VIEW_DISTANCE {
+            if let Some((_, _, agent)) = speaker_agent {
+                agent.add_context(&format!("{} is too far away to hear you.", listener_name));
+            }
+            continue;
+        }
+
+        if !dialogues.register(speaker_name, listener_name, time.elapsed_seconds()) {
+            if let Some((_, _, agent)) = speaker_agent {
+                agent.add_context(&format!(
+                    "{} is not listening to you anymore.",
+                    listener_name
+                ));
+            }
+            continue;
+        }
+
+        listener_agent.add_context(&format!(
+            "{} said to you: \"{}\"",
+            speaker_name, event.message
+        ));
+        if let Some((_, _, agent)) = speaker_agent {
+            agent.add_context(&format!(
+                "You said to {}: \"{}\"",
+                listener_name, event.message
+            ));
+        }
+    }
+}
+
+/// Creates the action taking the first step of a path, running when the path is long.
+fn first_path_action(path: &[ActionDirection]) -> Option<Action> {
+    let kind = if path.len() >= FOLLOW_RUN_DISTANCE {
+        ActionKind::Run
+    } else {
+        ActionKind::Walk
+    };
+    path.first()
+        .map(|direction| Action::new(kind, direction.clone()))
+}
+
+/// Searches the path toward a tile, and reserves its first step.
+///
+/// # Parameters
+/// - `terrain`: Relief levels of the loaded tiles.
+/// - `occupancy`: Tiles occupied by characters, avoided by the path.
+/// - `entity`: The bot walking.
+/// - `start`: The tile position of the bot.
+/// - `target`: The tile position to get close to.
+/// - `distance`: The distance to keep from the target, in tiles.
+fn take_path_step(
+    terrain: &Terrain,
+    occupancy: &mut Occupancy,
+    entity: Entity,
+    start: IVec2,
+    target: IVec2,
+    distance: i32,
+) -> PathStep {
+    let Some(path) = terrain.find_path(start, target, distance, BOT_VIEW_DISTANCE, |tile_pos| {
+        occupancy.blocker(tile_pos, entity).is_some()
+    }) else {
+        return PathStep::Unreachable;
+    };
+    let Some((next_action, terrain_cost)) =
+        first_path_action(&path).and_then(|next_action| terrain.plan_action(next_action, &start))
+    else {
+        return if path.is_empty() {
+            PathStep::Arrived
+        } else {
+            PathStep::Unreachable
+        };
+    };
+    if occupancy.reserve(entity, &next_action, &start).is_err() {
+        return PathStep::Blocked;
+    }
+    PathStep::Step(next_action, terrain_cost)
+}
+
+/// Clears the blocked status of a bot taking a step, or delays its next search.
+///
+/// # Returns
+/// The action to start with the cost of its destination, if any.
+fn handle_path_step(
+    commands: &mut Commands,
+    time: &Time,
+    entity: Entity,
+    target: IVec2,
+    step: PathStep,
+) -> Option<(Action, f32)> {
+    match step {
+        PathStep::Step(next_action, terrain_cost) => {
+            commands.entity(entity).remove::<(BlockedBy, PathBackoff)>();
+            Some((next_action, terrain_cost))
+        }
+        PathStep::Arrived => None,
+        PathStep::Unreachable | PathStep::Blocked => {
+            commands.entity(entity).insert(PathBackoff {
+                target,
+                until: time.elapsed_seconds() + BOT_PATH_RETRY_DELAY,
+            });
+            None
+        }
+    }
+}
+
+/// Checks if a bot still waits before searching a path to a tile again.
+fn is_backing_off(backoff: Option<&PathBackoff>, target: IVec2, time: &Time) -> bool {
+    backoff
+        .is_some_and(|backoff| backoff.target == target && time.elapsed_seconds() < backoff.until)
+}
+
+/// Describes where a character is relatively to the bot.
+fn describe_relative_position(name: &str, relative_position: IVec2) -> String {
+    let horizontal_direction = if relative_position.x > 0 {
+        "right"
+    } else {
+        "left"
+    };
+    let vertical_direction = if relative_position.y > 0 {
+        "up"
+    } else {
+        "down"
+    };
+
+    format!(
+        "{} is {} to your {} and {} to your {}\n",
+        name,
+        relative_position.x.abs(),
+        horizontal_direction,
+        relative_position.y.abs(),
+        vertical_direction
+    )
+}
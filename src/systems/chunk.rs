@@ -5,24 +5,17 @@ use bevy::log;
 use bevy::prelude::*;
 use bevy::tasks::AsyncComputeTaskPool;
 use bevy_ecs_tilemap::prelude::*;
-use once_cell::sync::Lazy;
 
 use crate::bundles::map::DataTileBundle;
 use crate::bundles::map::*;
+use crate::components::action::ActionDirection;
 use crate::components::map::*;
-use crate::constants::generation::CACHE_SIZE;
-use crate::constants::generation::LAYER_RANGE;
-use crate::constants::generation::NOISE_ZOOM;
-use crate::constants::generation::SAMPLE_NUMBER;
 use crate::constants::map::*;
 use crate::constants::tileset::*;
-use crate::util::noise::TiledNoise;
+use crate::util::noise::NOISE;
 use crate::util::position::*;
 use crate::util::tile::*;
 
-static NOISE: Lazy<TiledNoise> =
-    Lazy::new(|| TiledNoise::new(0, &LAYER_RANGE, NOISE_ZOOM, SAMPLE_NUMBER, CACHE_SIZE));
-
 // Compact layer definition
 struct LayerConfig<'a> {
     layer_index: u32,
@@ -269,6 +262,9 @@ fn setup_tile(world: &mut World, tile_config: LayeredTileConfig) {
     let level = NOISE.get_value(pos_x, pos_y);
     let mask = NOISE.get_mask(level, pos_x, pos_y);
     let is_edge = mask != 0;
+    let ramp = NOISE
+        .get_ramp(level, mask, pos_x, pos_y)
+        .and_then(|(dx, dy)| ActionDirection::from_vector(IVec2::new(dx, dy)));
     let id_0 = if !is_edge {
         get_random_tile_id(level)
     } else {
@@ -289,6 +285,10 @@ fn setup_tile(world: &mut World, tile_config: LayeredTileConfig) {
     world.entity_mut(layer_entity_0).add_child(tile_entity_0);
     tile_storage_0.set(&tile_pos, tile_entity_0);
 
+    if let Some(direction) = ramp.clone() {
+        world.entity_mut(tile_entity_0).insert(Ramp(direction));
+    }
+
     if let Some(animation) = TEXTURE_ANIMATION_MAP.lookup(&(level, id_0)) {
         world.entity_mut(tile_entity_0).insert(AnimatedTile {
             start: animation.start,
@@ -298,7 +298,11 @@ fn setup_tile(world: &mut World, tile_config: LayeredTileConfig) {
     }
 
     if is_edge {
-        let id_1 = mask_to_id(mask, level);
+        let id_1 = if ramp.is_some() {
+            TEXTURE_RAMP_IDS_MAP[&level]
+        } else {
+            mask_to_id(mask, level)
+        };
         let tile_bundle_1 = DataTileBundle {
             tile: TileBundle {
                 position: tile_pos,
@@ -0,0 +1,27 @@
+use bevy::prelude::*;
+
+use crate::components::clock::WorldClock;
+use crate::components::display::DaylightOverlay;
+
+/// Advances the in-game time.
+///
+/// # Parameters
+/// - `time`: Resource containing time information.
+/// - `clock`: Resource keeping the in-game time.
+pub fn advance_clock(time: Res<Time>, mut clock: ResMut<WorldClock>) {
+    clock.advance(time.delta_seconds());
+}
+
+/// Tints the world according to the time of day.
+///
+/// # Parameters
+/// - `clock`: Resource keeping the in-game time.
+/// - `query`: Query to access the overlay covering the world.
+pub fn update_daylight(
+    clock: Res<WorldClock>,
+    mut query: Query<&mut BackgroundColor, With<DaylightOverlay>>,
+) {
+    for mut color in query.iter_mut() {
+        *color = clock.tint().into();
+    }
+}
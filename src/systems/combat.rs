@@ -0,0 +1,137 @@
+use std::sync::atomic::Ordering;
+
+use bevy::log;
+use bevy::prelude::*;
+
+use crate::components::action::*;
+use crate::components::character::*;
+use crate::components::combat::{Hitbox, Knockback};
+use crate::components::map::{Occupancy, Terrain};
+use crate::components::texture::TilesetOffset;
+use crate::util::position::player_tile_pos;
+
+// Define a type for characters hit by an attack
+type TargetQuery<'a> = (
+    &'a Name,
+    &'a Busy,
+    &'a Action,
+    Has<Invulnerable>,
+    Option<&'a MovementMode>,
+);
+
+// Define a type for characters pushed by an attack
+type KnockbackQuery<'a> = (
+    Entity,
+    &'a Knockback,
+    &'a Busy,
+    &'a mut Action,
+    &'a mut ActionTimer,
+    &'a ActionDurationPHF,
+    &'a Transform,
+    &'a TilesetOffset,
+    Has<Dead>,
+);
+
+/// Resolves the attacks once their hitbox hits.
+///
+/// The occupant of the hitbox tile loses health and is knocked back, unless it
+/// blocks while facing the attacker. Attacks of dead characters are cancelled.
+///
+/// # Parameters
+/// - `commands`: Commands for despawning hitboxes and knocking characters back.
+/// - `time`: Resource containing time information.
+/// - `occupancy`: Tiles occupied by characters, finding who is hit.
+/// - `health_events`: For sending the attack damage.
+/// - `hitbox_query`: Query to access the pending hitboxes.
+/// - `target_query`: Query to access the living characters that can be hit.
+/// - `dead_query`: Query to check if an attacker died.
+pub fn resolve_hitboxes(
+    mut commands: Commands,
+    time: Res<Time>,
+    occupancy: Res<Occupancy>,
+    mut health_events: EventWriter<HealthChangeEvent>,
+    mut hitbox_query: Query<(Entity, &mut Hitbox)>,
+    target_query: Query<TargetQuery, (With<Health>, Without<Dead>)>,
+    dead_query: Query<(), With<Dead>>,
+) {
+    for (entity, mut hitbox) in hitbox_query.iter_mut() {
+        if !hitbox.timer.tick(time.delta()).finished() {
+            continue;
+        }
+        commands.entity(entity).despawn();
+        if dead_query.contains(hitbox.owner) {
+            continue;
+        }
+
+        let Some(target) = occupancy.blocker(&hitbox.tile_pos, hitbox.owner) else {
+            continue;
+        };
+        let Ok((name, busy, action, invulnerable, mode)) = target_query.get(target) else {
+            continue;
+        };
+
+        let is_facing_attacker = action
+            .direction
+            .to_vector()
+            .dot(hitbox.direction.to_vector())
+            < 0;
+        if busy.load(Ordering::Acquire) && action.kind == ActionKind::Block && is_facing_attacker {
+            log::info!("{} blocks an attack", name);
+            continue;
+        }
+
+        health_events.send(HealthChangeEvent {
+            entity: target,
+            amount: -(hitbox.damage as i32),
+            cause: HealthChangeCause::Attack(hitbox.owner),
+        });
+        // Freely moving characters are not on a tile to be pushed from
+        if !invulnerable && mode != Some(&MovementMode::Free) {
+            commands
+                .entity(target)
+                .insert(Knockback(hitbox.direction.clone()));
+        }
+    }
+}
+
+/// Pushes knocked back characters one tile away.
+///
+/// The push waits for the end of the current step and interrupts any other
+/// action. It is dropped when the destination is blocked or impassable, or
+/// when the character died.
+///
+/// # Parameters
+/// - `commands`: Commands for removing the applied knockbacks.
+/// - `terrain`: Relief levels of the loaded tiles.
+/// - `occupancy`: Tiles occupied by characters, reserved by the push.
+/// - `query`: Query to access the knocked back characters and their actions.
+pub fn apply_knockback(
+    mut commands: Commands,
+    terrain: Terrain,
+    mut occupancy: ResMut<Occupancy>,
+    mut query: Query<KnockbackQuery>,
+) {
+    for (entity, knockback, busy, mut action, mut timer, duration, transform, offset, dead) in
+        query.iter_mut()
+    {
+        if !dead && busy.load(Ordering::Acquire) && action.kind.is_moving() {
+            continue;
+        }
+        commands.entity(entity).remove::<Knockback>();
+        if dead {
+            continue;
+        }
+
+        let position = player_tile_pos(transform, offset);
+        let push = Action::new(ActionKind::Knockback, (**knockback).clone());
+        let Some((push, terrain_cost)) = terrain.plan_action(push, &position) else {
+            continue;
+        };
+        if occupancy.reserve(entity, &push, &position).is_err() {
+            continue;
+        }
+        *action = push;
+        *timer = duration.generate_timer(&action, terrain_cost);
+        busy.store(true, Ordering::Release);
+    }
+}
@@ -0,0 +1,21 @@
+use bevy::prelude::*;
+
+use crate::components::display::Lifetime;
+
+/// Despawns the entities whose lifetime is over.
+///
+/// # Parameters
+/// - `commands`: Commands for despawning entities.
+/// - `time`: Resource containing time information.
+/// - `query`: Query for accessing and ticking lifetimes.
+pub fn despawn_expired(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Lifetime)>,
+) {
+    for (entity, mut lifetime) in query.iter_mut() {
+        if lifetime.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
@@ -0,0 +1,204 @@
+use std::fs;
+use std::path::Path;
+
+use bevy::app::AppExit;
+use bevy::log;
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+
+use crate::components::character::{IsBot, IsUser};
+use crate::components::exploration::*;
+use crate::components::map::{ChunkMap, Terrain};
+use crate::components::texture::TilesetOffset;
+use crate::constants::exploration::*;
+use crate::constants::map::{CHUNK_SIZE, CHUNK_TILES, TILE};
+use crate::util::noise::NOISE;
+use crate::util::position::{player_tile_pos, tile_pos_to_chunk_pos};
+
+// Define a filter matching users and bots
+type IsCharacter = Or<(With<IsUser>, With<IsBot>)>;
+
+/// Loads the explored tiles from the save file.
+///
+/// # Parameters
+/// - `commands`: For inserting the explored tiles resource.
+///
+/// Nothing is explored when the file is missing or invalid, which is always
+/// the case on the web. The relief levels are generated again from the noise.
+pub fn load_explored(mut commands: Commands) {
+    let save = fs::read_to_string(EXPLORED_SAVE_PATH)
+        .ok()
+        .and_then(|save| {
+            serde_json::from_str::<ExploredSave>(&save)
+                .map_err(|e| log::warn!("Invalid save {}: {}", EXPLORED_SAVE_PATH, e))
+                .ok()
+        })
+        .unwrap_or_default();
+
+    let mut explored = ExploredMap::default();
+    for (chunk_pos, chunk_explored) in save.chunks {
+        let base = chunk_pos * CHUNK_SIZE.as_ivec2();
+        let mut levels = [0; CHUNK_TILES];
+        for (index, level) in levels.iter_mut().enumerate() {
+            let position = base + chunk_tile_offset(index);
+            *level = NOISE.get_value(position.x, position.y) as u8;
+        }
+        explored.insert(
+            chunk_pos,
+            ExploredChunk {
+                levels,
+                explored: chunk_explored,
+            },
+        );
+    }
+    log::info!("Loaded {} explored chunks", explored.len());
+    commands.insert_resource(explored);
+}
+
+/// Remembers the relief levels of the generated chunks.
+///
+/// # Parameters
+/// - `chunk_map`: Resource containing all chunk data.
+/// - `terrain`: Relief levels of the loaded tiles.
+/// - `explored`: Resource remembering the generated chunks and their explored tiles.
+pub fn record_explored_chunks(
+    chunk_map: Res<ChunkMap>,
+    terrain: Terrain,
+    mut explored: ResMut<ExploredMap>,
+) {
+    for chunk_pos in chunk_map.keys() {
+        if explored.contains_key(chunk_pos) {
+            continue;
+        }
+
+        // The tiles of a chunk are generated asynchronously
+        let base = *chunk_pos * CHUNK_SIZE.as_ivec2();
+        let mut levels = [0; CHUNK_TILES];
+        let is_generated = levels.iter_mut().enumerate().all(|(index, level)| {
+            terrain
+                .level(&(base + chunk_tile_offset(index)))
+                .map(|tile_level| *level = tile_level as u8)
+                .is_some()
+        });
+        if is_generated {
+            explored.insert(
+                *chunk_pos,
+                ExploredChunk {
+                    levels,
+                    explored: Explored::default(),
+                },
+            );
+        }
+    }
+}
+
+/// Explores the tiles around users and bots.
+///
+/// # Parameters
+/// - `explored`: Resource remembering the generated chunks and their explored tiles.
+/// - `character_query`: Query to access the positions of users and bots.
+pub fn explore_tiles(
+    mut explored: ResMut<ExploredMap>,
+    character_query: Query<(&Transform, &TilesetOffset), IsCharacter>,
+) {
+    for (transform, offset) in character_query.iter() {
+        let position = player_tile_pos(transform, offset);
+        for y in -EXPLORE_RADIUS..=EXPLORE_RADIUS {
+            for x in -EXPLORE_RADIUS..=EXPLORE_RADIUS {
+                let tile_pos = position + IVec2::new(x, y);
+                if x * x + y * y > EXPLORE_RADIUS * EXPLORE_RADIUS
+                    || explored.is_explored(&tile_pos)
+                {
+                    continue;
+                }
+                // Only mutated when something is new, the maps follow the changes
+                if let Some(chunk) = explored.get_mut(&tile_pos_to_chunk_pos(&tile_pos)) {
+                    chunk.explored.insert(&tile_pos);
+                }
+            }
+        }
+    }
+}
+
+/// Darkens the unexplored tiles of the loaded chunks.
+///
+/// # Parameters
+/// - `explored`: Resource remembering the explored tiles.
+/// - `layer_query`: Query to access the position of the tilemap layers.
+/// - `tile_query`: Query to access and color the tiles.
+/// - `added_query`: Query to detect the newly spawned tiles.
+pub fn darken_unexplored_tiles(
+    explored: Res<ExploredMap>,
+    layer_query: Query<&Transform, With<TileStorage>>,
+    mut tile_query: Query<(&TilePos, &TilemapId, &mut TileColor)>,
+    added_query: Query<(), Added<TilePos>>,
+) {
+    if !explored.is_changed() && added_query.is_empty() {
+        return;
+    }
+
+    for (tile_pos, tilemap_id, mut color) in tile_query.iter_mut() {
+        let Ok(transform) = layer_query.get(tilemap_id.0) else {
+            continue;
+        };
+        let base = (transform.translation.xy() / TILE).round().as_ivec2();
+        let position = base + IVec2::new(tile_pos.x as i32, tile_pos.y as i32);
+        let new_color = if explored.is_explored(&position) {
+            Color::WHITE
+        } else {
+            UNEXPLORED_TILE_COLOR
+        };
+        if color.0 != new_color {
+            color.0 = new_color;
+        }
+    }
+}
+
+/// Saves the explored tiles at a fixed interval and when the game exits.
+///
+/// Runs in `Last`, as the exit event is only sent in `PostUpdate` of the
+/// final frame.
+///
+/// # Parameters
+/// - `time`: Resource containing time information.
+/// - `explored`: Resource remembering the explored tiles.
+/// - `autosave`: Resource timing the saves.
+/// - `exit_events`: Sent when the game exits.
+/// - `is_dirty`: Whether tiles were explored since the last save.
+pub fn save_explored(
+    time: Res<Time>,
+    explored: Res<ExploredMap>,
+    mut autosave: ResMut<ExploredAutosave>,
+    mut exit_events: EventReader<AppExit>,
+    mut is_dirty: Local<bool>,
+) {
+    *is_dirty |= explored.is_changed();
+    let is_exiting = exit_events.read().count() > 0;
+    if !autosave.tick(time.delta()).just_finished() && !is_exiting {
+        return;
+    }
+    if !*is_dirty {
+        return;
+    }
+
+    let result = serde_json::to_string(&explored.to_save())
+        .map_err(|e| e.to_string())
+        .and_then(|save| {
+            if let Some(directory) = Path::new(EXPLORED_SAVE_PATH).parent() {
+                fs::create_dir_all(directory).map_err(|e| e.to_string())?;
+            }
+            fs::write(EXPLORED_SAVE_PATH, save).map_err(|e| e.to_string())
+        });
+    match result {
+        Ok(()) => *is_dirty = false,
+        Err(e) => log::warn!("Could not save {}: {}", EXPLORED_SAVE_PATH, e),
+    }
+}
+
+/// Gets the position of a tile in its chunk from its index, row by row.
+fn chunk_tile_offset(index: usize) -> IVec2 {
+    IVec2::new(
+        (index as u32 % CHUNK_SIZE.x) as i32,
+        (index as u32 / CHUNK_SIZE.x) as i32,
+    )
+}
@@ -0,0 +1,263 @@
+use std::sync::atomic::Ordering;
+
+use bevy::log;
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+
+use crate::components::action::{Action, ActionTimer};
+use crate::components::character::*;
+use crate::components::command::ActionQueue;
+use crate::components::display::HealthBar;
+use crate::components::map::Terrain;
+use crate::components::texture::TilesetOffset;
+use crate::constants::action::PLAYER_ACTION_DEFAULT;
+use crate::constants::character::*;
+use crate::constants::generation::WATER_LEVEL;
+use crate::util::position::player_tile_pos;
+
+// Define a type for characters changing health
+type HealthQuery<'a> = (
+    &'a Name,
+    &'a mut Health,
+    &'a MaxHealth,
+    Option<&'a Invulnerable>,
+    &'a Busy,
+    &'a mut Action,
+    &'a mut ActionTimer,
+    &'a mut Visibility,
+    Option<&'a ActionQueue>,
+    Option<&'a mut Velocity>,
+);
+
+// Define a type for respawning characters
+type RespawnQuery<'a> = (
+    Entity,
+    &'a Busy,
+    &'a mut Health,
+    &'a MaxHealth,
+    &'a SpawnPoint,
+    &'a TilesetOffset,
+    &'a mut Transform,
+    &'a mut Visibility,
+    &'a mut Footing,
+    &'a mut Submersion,
+    Option<&'a mut Velocity>,
+);
+
+/// Damages characters falling from a ledge.
+///
+/// # Parameters
+/// - `terrain`: Relief levels of the loaded tiles.
+/// - `health_events`: For sending the fall damage.
+/// - `query`: Query to access the position and footing of living characters.
+pub fn apply_fall_damage(
+    terrain: Terrain,
+    mut health_events: EventWriter<HealthChangeEvent>,
+    mut query: Query<(Entity, &Transform, &TilesetOffset, &mut Footing), Without<Dead>>,
+) {
+    for (entity, transform, offset, mut footing) in query.iter_mut() {
+        let tile_pos = player_tile_pos(transform, offset);
+        if **footing == Some(tile_pos) || terrain.level(&tile_pos).is_none() {
+            continue;
+        }
+
+        let height = footing.map_or(0, |previous| terrain.fall_height(&previous, &tile_pos));
+        if height > 0 {
+            health_events.send(HealthChangeEvent {
+                entity,
+                amount: -((height * FALL_DAMAGE as u32) as i32),
+                cause: HealthChangeCause::Fall,
+            });
+        }
+        **footing = Some(tile_pos);
+    }
+}
+
+/// Damages characters staying in water longer than `SWIM_ENDURANCE`.
+///
+/// # Parameters
+/// - `time`: Resource containing time information.
+/// - `terrain`: Relief levels of the loaded tiles.
+/// - `health_events`: For sending the drowning damage.
+/// - `query`: Query to access the position and time in water of living characters.
+pub fn apply_drowning(
+    time: Res<Time>,
+    terrain: Terrain,
+    mut health_events: EventWriter<HealthChangeEvent>,
+    mut query: Query<(Entity, &Transform, &TilesetOffset, &mut Submersion), Without<Dead>>,
+) {
+    for (entity, transform, offset, mut submersion) in query.iter_mut() {
+        if terrain.level(&player_tile_pos(transform, offset)) != Some(WATER_LEVEL) {
+            **submersion = 0.;
+            continue;
+        }
+
+        // Damage once per interval past the endurance
+        let intervals = |seconds: f32| ((seconds - SWIM_ENDURANCE) / DROWNING_INTERVAL).floor();
+        let previous = **submersion;
+        **submersion += time.delta_seconds();
+        if **submersion >= SWIM_ENDURANCE && intervals(**submersion) > intervals(previous) {
+            health_events.send(HealthChangeEvent {
+                entity,
+                amount: -(DROWNING_DAMAGE as i32),
+                cause: HealthChangeCause::Drowning,
+            });
+        }
+    }
+}
+
+/// Applies the health changes to characters.
+///
+/// Damage makes characters invulnerable for `INVULNERABILITY_DURATION`.
+/// Characters without health die: they are hidden, stop acting and forget
+/// their queued commands until they respawn.
+///
+/// # Parameters
+/// - `commands`: Commands for marking invulnerable and dead characters.
+/// - `health_events`: Health changes sent this frame.
+/// - `query`: Query to access the health and action of characters.
+pub fn apply_health_changes(
+    mut commands: Commands,
+    mut health_events: EventReader<HealthChangeEvent>,
+    mut query: Query<HealthQuery, Without<Dead>>,
+) {
+    // Invulnerability is only inserted at the end of the frame
+    let mut damaged = HashSet::new();
+
+    for event in health_events.read() {
+        let Ok((
+            name,
+            mut health,
+            max_health,
+            invulnerable,
+            busy,
+            mut action,
+            mut timer,
+            mut visibility,
+            queue,
+            velocity,
+        )) = query.get_mut(event.entity)
+        else {
+            continue;
+        };
+
+        if event.amount < 0 {
+            if invulnerable.is_some() || !damaged.insert(event.entity) {
+                continue;
+            }
+            commands
+                .entity(event.entity)
+                .insert(Invulnerable(Timer::from_seconds(
+                    INVULNERABILITY_DURATION,
+                    TimerMode::Once,
+                )));
+        }
+
+        **health = (**health as i32 + event.amount).clamp(0, **max_health as i32) as u8;
+        log::info!(
+            "{} {} {} health from {}, {} left",
+            name,
+            if event.amount < 0 { "loses" } else { "gains" },
+            event.amount.abs(),
+            event.cause,
+            **health
+        );
+
+        if **health == 0 {
+            log::info!("{} dies", name);
+            commands.entity(event.entity).insert(Dead);
+            *visibility = Visibility::Hidden;
+            if let Some(queue) = queue {
+                queue.clear();
+            }
+            if let Some(mut velocity) = velocity {
+                **velocity = Vec2::ZERO;
+            }
+            // Waiting for the respawn keeps the character busy
+            *action = Action::new(PLAYER_ACTION_DEFAULT.kind, action.direction.clone());
+            *timer = ActionTimer(Timer::from_seconds(RESPAWN_DELAY, TimerMode::Once));
+            busy.store(true, Ordering::Release);
+        }
+    }
+}
+
+/// Makes invulnerable characters blink until their invulnerability ends.
+///
+/// # Parameters
+/// - `commands`: Commands for removing finished invulnerability.
+/// - `time`: Resource containing time information.
+/// - `query`: Query to access the invulnerability and sprite of characters.
+pub fn update_invulnerability(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Invulnerable, &mut TextureAtlasSprite)>,
+) {
+    for (entity, mut invulnerable, mut sprite) in query.iter_mut() {
+        invulnerable.tick(time.delta());
+        if invulnerable.finished() {
+            commands.entity(entity).remove::<Invulnerable>();
+            sprite.color.set_a(1.);
+            continue;
+        }
+
+        // Faded during the first half of every blink period
+        let blink =
+            invulnerable.elapsed_secs() % (2. * INVULNERABILITY_BLINK) < INVULNERABILITY_BLINK;
+        sprite
+            .color
+            .set_a(if blink { INVULNERABILITY_ALPHA } else { 1. });
+    }
+}
+
+/// Respawns dead characters at their spawn point once their respawn delay is over.
+///
+/// # Parameters
+/// - `commands`: Commands for removing the dead status.
+/// - `query`: Query to access dead characters.
+pub fn respawn(mut commands: Commands, mut query: Query<RespawnQuery, With<Dead>>) {
+    for (
+        entity,
+        busy,
+        mut health,
+        max_health,
+        spawn_point,
+        offset,
+        mut transform,
+        mut visibility,
+        mut footing,
+        mut submersion,
+        velocity,
+    ) in query.iter_mut()
+    {
+        if busy.load(Ordering::Acquire) {
+            continue;
+        }
+
+        commands.entity(entity).remove::<Dead>();
+        **health = **max_health;
+        transform.translation = (**spawn_point + **offset).extend(transform.translation.z);
+        *visibility = Visibility::Visible;
+        **footing = None;
+        **submersion = 0.;
+        if let Some(mut velocity) = velocity {
+            **velocity = Vec2::ZERO;
+        }
+    }
+}
+
+/// Updates the HUD health bar with the health of the user.
+///
+/// # Parameters
+/// - `user_query`: Query to access the health of the user.
+/// - `bar_query`: Query to access and resize the health bar.
+pub fn update_health_bar(
+    user_query: Query<(&Health, &MaxHealth), With<IsUser>>,
+    mut bar_query: Query<&mut Style, With<HealthBar>>,
+) {
+    let Ok((health, max_health)) = user_query.get_single() else {
+        return;
+    };
+    for mut style in bar_query.iter_mut() {
+        style.width = Val::Percent(**health as f32 * 100. / (**max_health).max(1) as f32);
+    }
+}
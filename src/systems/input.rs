@@ -1,229 +1,722 @@
 use std::sync::atomic::Ordering;
 
+use std::fs;
+
 use bevy::input::Input;
 use bevy::prelude::*;
-use bevy_ecs_tilemap::tiles::TileStorage;
+use bevy::window::PrimaryWindow;
 
 use crate::components::action::*;
 use crate::components::character::*;
-use crate::components::gpt::GPTAgent;
-use crate::components::map::ChunkMap;
-use crate::components::map::ReliefLevel;
+use crate::components::combat::Hitbox;
+use crate::components::command::{ActionQueue, AttackTarget, Command, Destination, FollowTarget};
+use crate::components::display::{IsGameCamera, TileCursor};
+use crate::components::gpt::BotSpeechEvent;
+use crate::components::input::{ActionInput, InputAction, InputBindings};
+use crate::components::interaction::{Interactable, InteractionEvent};
+use crate::components::item::{ItemAction, ItemActionEvent};
+use crate::components::map::{Occupancy, Terrain};
+use crate::components::quest::QuestProposalEvent;
 use crate::components::texture::TilesetOffset;
-use crate::constants::action::PLAYER_ACTION_DEFAULT;
+use crate::constants::action::*;
+use crate::constants::character::{PLAYER_ATTACK_DAMAGE, USER_PATH_RADIUS, USER_PATH_RUN_DISTANCE};
+use crate::constants::display::TILE_CURSOR_LAYER;
+use crate::constants::generation::WATER_LEVEL;
+use crate::constants::input::{DEFAULT_INPUT_CONFIG, INPUT_CONFIG_PATH};
+use crate::constants::map::TILE;
 use crate::constants::sprites::*;
 use crate::util::effect::spawn_effect;
-use crate::util::position::player_tile_pos;
-use crate::util::position::relative_tile_pos;
-use crate::util::position::tile_pos_to_chunk_pos;
+use crate::util::position::{
+    action_start_tile_pos, pixel_pos_to_tile_pos, player_nearest_tile_pos, player_tile_pos,
+};
 
 // Define a type for player character queries
 type PlayerCharacterQuery<'a> = (
+    Entity,
     &'a mut Busy,
     &'a mut Action,
     &'a mut ActionTimer,
     &'a ActionDurationPHF,
     &'a Transform,
     &'a TilesetOffset,
+    &'a MovementMode,
+    &'a ActionQueue,
+);
+
+// Define a type for users moving to a clicked tile
+type ClickMovingUserQuery<'a> = (
+    Entity,
+    &'a Busy,
+    &'a Action,
+    &'a ActionTimer,
+    &'a Motion,
+    &'a Transform,
+    &'a TilesetOffset,
+    &'a MovementMode,
+    &'a ActionQueue,
+);
+
+// Define a type for free moving character queries
+type FreeCharacterQuery<'a> = (
+    &'a Busy,
+    &'a MovementMode,
+    &'a mut Velocity,
+    &'a mut Action,
+    &'a Transform,
+    &'a TilesetOffset,
+);
+
+// Define a type for movement mode queries
+type MovementModeQuery<'a> = (
+    &'a Busy,
+    &'a mut MovementMode,
+    &'a mut Velocity,
+    &'a mut Action,
+    &'a mut Transform,
+    &'a TilesetOffset,
 );
 
 // Define a type for bot character queries
 type BotCharacterQuery<'a> = (
+    Entity,
     &'a mut Busy,
     &'a mut Action,
     &'a mut ActionTimer,
     &'a ActionDurationPHF,
     &'a Transform,
     &'a TilesetOffset,
-    &'a GPTAgent,
+    &'a ActionQueue,
 );
 
-/// Key codes
-const KEY_UP: [KeyCode; 2] = [KeyCode::Up, KeyCode::W];
-const KEY_LEFT: [KeyCode; 2] = [KeyCode::Left, KeyCode::A];
-const KEY_DOWN: [KeyCode; 2] = [KeyCode::Down, KeyCode::S];
-const KEY_RIGHT: [KeyCode; 2] = [KeyCode::Right, KeyCode::D];
-const KEY_RUN: [KeyCode; 2] = [KeyCode::ShiftLeft, KeyCode::ShiftRight];
-const KEY_TALK: [KeyCode; 1] = [KeyCode::T];
+/// Loads the input bindings from the input config file.
+///
+/// # Parameters
+/// - `commands`: For inserting the bindings and input action resources.
+///
+/// The embedded default config is used when the file is missing or invalid,
+/// which is always the case on the web.
+pub fn load_input_bindings(mut commands: Commands) {
+    let bindings = fs::read_to_string(INPUT_CONFIG_PATH)
+        .ok()
+        .and_then(|config| {
+            InputBindings::from_config(&config)
+                .map_err(|e| warn!("Invalid input config {}: {}", INPUT_CONFIG_PATH, e))
+                .ok()
+        })
+        .unwrap_or_else(|| {
+            InputBindings::from_config(DEFAULT_INPUT_CONFIG)
+                .expect("The default input config should be valid")
+        });
+
+    commands.insert_resource(bindings);
+    commands.insert_resource(ActionInput::default());
+}
 
-/// Handles keyboard input for player characters.
+/// Translates the state of the keyboard and gamepads into input actions.
 ///
 /// # Parameters
-/// - `commands`: For spawning entities
+/// - `bindings`: The keys, buttons and axes bound to each input action.
+/// - `action_input`: The input actions of the current frame.
 /// - `keyboard_input`: The current state of the keyboard.
+/// - `gamepads`: The connected gamepads.
+/// - `button_input`: The current state of the gamepad buttons.
+/// - `axes`: The current state of the gamepad axes.
+///
+/// Direction keys and buttons give a digital movement, sticks an analog one past the deadzone.
+pub fn update_action_input(
+    bindings: Res<InputBindings>,
+    mut action_input: ResMut<ActionInput>,
+    keyboard_input: Res<Input<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    button_input: Res<Input<GamepadButton>>,
+    axes: Res<Axis<GamepadAxis>>,
+) {
+    action_input.clear();
+
+    for binding in bindings.bindings.iter() {
+        let buttons = || {
+            gamepads.iter().flat_map(|gamepad| {
+                binding
+                    .buttons
+                    .iter()
+                    .map(move |button_type| GamepadButton::new(gamepad, *button_type))
+            })
+        };
+        let pressed = keyboard_input.any_pressed(binding.keys.iter().copied())
+            || button_input.any_pressed(buttons());
+        if pressed {
+            let just_pressed = keyboard_input.any_just_pressed(binding.keys.iter().copied())
+                || button_input.any_just_pressed(buttons());
+            action_input.press(binding.action, just_pressed);
+        }
+    }
+
+    // Opposite directions cancel each other
+    let axis = |negative: InputAction, positive: InputAction| {
+        action_input.pressed(positive) as i32 as f32 - action_input.pressed(negative) as i32 as f32
+    };
+    let mut movement = Vec2::new(
+        axis(InputAction::MoveLeft, InputAction::MoveRight),
+        axis(InputAction::MoveDown, InputAction::MoveUp),
+    );
+
+    for gamepad in gamepads.iter() {
+        let stick = Vec2::new(
+            axes.get(GamepadAxis::new(gamepad, bindings.move_axis_x))
+                .unwrap_or(0.),
+            axes.get(GamepadAxis::new(gamepad, bindings.move_axis_y))
+                .unwrap_or(0.),
+        );
+        // Rescale past the deadzone so the movement still starts from zero
+        let deflection = stick.length().min(1.);
+        if deflection > bindings.deadzone {
+            let strength = (deflection - bindings.deadzone) / (1. - bindings.deadzone);
+            movement += stick.normalize() * strength;
+        }
+    }
+
+    action_input.movement = movement.clamp_length_max(1.);
+}
+
+/// Handles input actions for player characters.
+///
+/// # Parameters
+/// - `commands`: For spawning entities
+/// - `action_input`: The input actions of the current frame.
+/// - `keyboard_input`: The current state of the keyboard, any key cancels the queued steps.
 /// - `query`: Query for accessing and modifying the components related to user actions.
-/// - `chunk_map`: Resource providing the game's chunk map.
-/// - `chunk_query`: Query for accessing tile storage data.
-/// - `tile_query`: Query for accessing relief level of tiles.
+/// - `terrain`: Relief levels of the loaded tiles.
+/// - `occupancy`: Tiles occupied by characters, moves into them are rejected.
+/// - `interactable_query`: Query to find the interactable entity faced by the user.
+/// - `interaction_events`: For sending the interactions of the user.
+/// - `item_events`: For sending what the user does with items.
 /// - `asset_server`: For getting textures
 /// - `texture_atlas`: Registering / spawning textures
 ///
-/// This function processes the keyboard inputs and updates the actions of the player character accordingly.
+/// This function processes the input actions and updates the actions of the player character accordingly.
+/// Without input, the steps queued by `handle_click_input` are taken one by one.
+/// Characters in free movement mode are moved by `handle_free_input`, only their
+/// actions in place start here, from their nearest tile.
 #[allow(clippy::too_many_arguments)]
 pub fn handle_input(
     mut commands: Commands,
     mut query: Query<PlayerCharacterQuery, With<IsUser>>,
+    action_input: Res<ActionInput>,
     keyboard_input: Res<Input<KeyCode>>,
-    chunk_map: Res<ChunkMap>,
-    chunk_query: Query<&TileStorage>,
-    tile_query: Query<&ReliefLevel>,
+    terrain: Terrain,
+    mut occupancy: ResMut<Occupancy>,
+    interactable_query: Query<(), With<Interactable>>,
+    mut interaction_events: EventWriter<InteractionEvent>,
+    mut item_events: EventWriter<ItemActionEvent>,
     asset_server: Res<AssetServer>,
     mut texture_atlas: ResMut<Assets<TextureAtlas>>,
 ) {
-    for (busy, mut action, mut timer, duration, transform, offset) in query.iter_mut() {
+    for (entity, busy, mut action, mut timer, duration, transform, offset, mode, queue) in
+        query.iter_mut()
+    {
+        // Item actions are instant, the first carried item is dropped or given to the faced character.
+        // They don't depend on the tile steps, so free moving users use them too
+        let item_action = if action_input.just_pressed(InputAction::PickUp) {
+            Some(ItemAction::PickUp)
+        } else if action_input.just_pressed(InputAction::Drop) {
+            Some(ItemAction::Drop {
+                item: None,
+                count: 1,
+            })
+        } else if action_input.just_pressed(InputAction::Give) {
+            Some(ItemAction::Give {
+                item: None,
+                count: 1,
+                target: None,
+            })
+        } else if action_input.just_pressed(InputAction::UseItem) {
+            Some(ItemAction::Use { item: None })
+        } else {
+            None
+        };
+        if let Some(item_action) = item_action {
+            item_events.send(ItemActionEvent {
+                actor: entity,
+                action: item_action,
+            });
+        }
+
+        let is_free = *mode == MovementMode::Free;
+        if !is_free
+            && (keyboard_input.get_just_pressed().next().is_some()
+                || action_input.direction().is_some())
+        {
+            queue.clear();
+        }
+
         if busy.load(Ordering::Acquire) {
-            return;
+            continue;
+        }
+
+        if is_free {
+            let new_action = if action_input.just_pressed(InputAction::Attack) {
+                Action::new(ActionKind::Attack, action.direction.clone())
+            } else if action_input.pressed(InputAction::Block) {
+                Action::new(ActionKind::Block, action.direction.clone())
+            } else if action_input.just_pressed(InputAction::Interact) {
+                Action::new(ActionKind::Interact, action.direction.clone())
+            } else {
+                continue;
+            };
+            let position = player_nearest_tile_pos(transform, offset);
+            match new_action.kind {
+                ActionKind::Interact => send_interaction(
+                    entity,
+                    &new_action,
+                    &position,
+                    &occupancy,
+                    &interactable_query,
+                    &mut interaction_events,
+                ),
+                ActionKind::Attack => {
+                    commands.spawn(Hitbox::new(
+                        entity,
+                        &new_action,
+                        &position,
+                        PLAYER_ATTACK_DAMAGE,
+                        duration.hit_delay(),
+                    ));
+                }
+                _ => {}
+            }
+            *action = new_action;
+            *timer = duration.generate_timer(&action, 1.);
+            busy.store(true, Ordering::Release);
+            continue;
         }
 
-        let action_kind = if keyboard_input.any_pressed(KEY_TALK) {
+        let action_kind = if action_input.pressed(InputAction::Talk) {
             ActionKind::Type
-        } else if keyboard_input.any_pressed(KEY_RUN) {
+        } else if action_input.pressed(InputAction::Run) {
             ActionKind::Run
         } else {
             ActionKind::Walk
         };
 
-        let new_action_option = if keyboard_input.any_pressed(KEY_DOWN) {
-            Some(Action::new(action_kind, ActionDirection::Down))
-        } else if keyboard_input.any_pressed(KEY_UP) {
-            Some(Action::new(action_kind, ActionDirection::Up))
-        } else if keyboard_input.any_pressed(KEY_LEFT) {
-            Some(Action::new(action_kind, ActionDirection::Left))
-        } else if keyboard_input.any_pressed(KEY_RIGHT) {
-            Some(Action::new(action_kind, ActionDirection::Right))
-        } else if keyboard_input.any_pressed(KEY_TALK) {
+        let new_action_option = if action_input.just_pressed(InputAction::Attack) {
+            Some(Action::new(ActionKind::Attack, action.direction.clone()))
+        } else if action_input.pressed(InputAction::Block) {
+            Some(Action::new(ActionKind::Block, action.direction.clone()))
+        } else if let Some(direction) = action_input.direction() {
+            Some(Action::new(action_kind, direction))
+        } else if action_input.pressed(InputAction::Talk) {
             spawn_effect(
                 &mut commands,
                 &TYPE_EFFECT_SPRITE_GRID,
                 TYPE_EFFECT.clone(),
                 transform.translation + BUBBLE_RELATIVE_POSITION,
+                TYPE_EFFECT_DURATION,
                 &asset_server.load(TYPE_EFFECT_SPRITE),
                 &mut texture_atlas,
             );
             Some(Action::new(action_kind, ActionDirection::Down))
+        } else if action_input.just_pressed(InputAction::Interact) {
+            Some(Action::new(ActionKind::Interact, action.direction.clone()))
+        } else if let Some(Command::Act(queued_action)) = queue.pop_front() {
+            Some(queued_action)
         } else {
             None
         };
 
-        match new_action_option {
-            Some(new_action)
-                if is_action_possible(
-                    &new_action,
-                    transform,
-                    offset,
-                    &chunk_map,
-                    &chunk_query,
-                    &tile_query,
-                ) =>
+        let position = player_tile_pos(transform, offset);
+        match new_action_option.and_then(|new_action| terrain.plan_action(new_action, &position)) {
+            Some((new_action, terrain_cost))
+                if occupancy.reserve(entity, &new_action, &position).is_ok() =>
             {
+                if new_action.kind == ActionKind::Interact {
+                    send_interaction(
+                        entity,
+                        &new_action,
+                        &position,
+                        &occupancy,
+                        &interactable_query,
+                        &mut interaction_events,
+                    );
+                }
+                if new_action.kind == ActionKind::Attack {
+                    commands.spawn(Hitbox::new(
+                        entity,
+                        &new_action,
+                        &position,
+                        PLAYER_ATTACK_DAMAGE,
+                        duration.hit_delay(),
+                    ));
+                }
                 *action = new_action;
-                *timer = duration.generate_timer(&action);
+                *timer = duration.generate_timer(&action, terrain_cost);
                 busy.store(true, Ordering::Release);
             }
-            _ => action.kind = PLAYER_ACTION_DEFAULT.kind,
+            _ => {
+                // The way to the clicked tile is blocked
+                queue.clear();
+                action.kind = PLAYER_ACTION_DEFAULT.kind;
+            }
+        }
+    }
+}
+
+/// Handles mouse clicks and touches moving player characters to a tile.
+///
+/// # Parameters
+/// - `mouse_input`: The current state of the mouse buttons.
+/// - `touches`: The current touches on the screen.
+/// - `window_query`: Query to access the primary window and its cursor.
+/// - `camera_query`: Query to access the game camera, converting the cursor to world positions.
+/// - `cursor_query`: Query to access and move the tile cursor.
+/// - `terrain`: Relief levels of the loaded tiles.
+/// - `occupancy`: Tiles occupied by characters, avoided by the path.
+/// - `user_query`: Query to access users and their queued steps.
+/// - `pending_target`: Clicked tile waiting for a locked queue, retried next frame.
+///
+/// The tile cursor highlights the hovered or touched tile. A click or a tap queues
+/// the steps of the shortest path to the tile, ignored when no path leads to it.
+/// Characters in free movement mode are not moved.
+#[allow(clippy::too_many_arguments)]
+pub fn handle_click_input(
+    mouse_input: Res<Input<MouseButton>>,
+    touches: Res<Touches>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<IsGameCamera>>,
+    mut cursor_query: Query<(&mut Transform, &mut Visibility), With<TileCursor>>,
+    terrain: Terrain,
+    occupancy: Res<Occupancy>,
+    user_query: Query<ClickMovingUserQuery, (With<IsUser>, Without<TileCursor>)>,
+    mut pending_target: Local<Option<IVec2>>,
+) {
+    let (Ok(window), Ok((camera, camera_transform))) =
+        (window_query.get_single(), camera_query.get_single())
+    else {
+        return;
+    };
+    let to_tile_pos = |screen_pos: Vec2| {
+        camera
+            .viewport_to_world_2d(camera_transform, screen_pos)
+            .map(|world_pos| pixel_pos_to_tile_pos(&world_pos))
+    };
+
+    let hovered_tile_pos = window.cursor_position().and_then(to_tile_pos);
+    let touched_tile_pos = touches
+        .iter_just_pressed()
+        .next()
+        .and_then(|touch| to_tile_pos(touch.position()));
+
+    if let Ok((mut cursor_transform, mut visibility)) = cursor_query.get_single_mut() {
+        match touched_tile_pos.or(hovered_tile_pos) {
+            Some(tile_pos) => {
+                cursor_transform.translation =
+                    (tile_pos.as_vec2() * TILE).extend(TILE_CURSOR_LAYER);
+                *visibility = Visibility::Visible;
+            }
+            None => *visibility = Visibility::Hidden,
+        }
+    }
+
+    let clicked_tile_pos = hovered_tile_pos.filter(|_| mouse_input.just_pressed(MouseButton::Left));
+    let Some(target) = touched_tile_pos
+        .or(clicked_tile_pos)
+        .or(pending_target.take())
+    else {
+        return;
+    };
+
+    for (entity, busy, action, timer, motion, transform, offset, mode, queue) in user_query.iter() {
+        if *mode == MovementMode::Free {
+            continue;
+        }
+
+        // The path starts where the current step ends
+        let start = if busy.load(Ordering::Acquire) && action.kind.is_moving() {
+            action_start_tile_pos(transform, offset, motion, timer)
+                + action.get_raw_transformation()
+        } else {
+            player_tile_pos(transform, offset)
+        };
+        let Some(path) = terrain.find_path(start, target, 0, USER_PATH_RADIUS, |tile_pos| {
+            occupancy.blocker(tile_pos, entity).is_some()
+        }) else {
+            continue;
+        };
+
+        let kind = if path.len() >= USER_PATH_RUN_DISTANCE {
+            ActionKind::Run
+        } else {
+            ActionKind::Walk
+        };
+        let steps = path
+            .into_iter()
+            .map(|direction| Command::Act(Action::new(kind.clone(), direction)));
+        if !queue.replace(steps) {
+            *pending_target = Some(target);
+        }
+    }
+}
+
+/// Handles input actions for player characters in free movement mode.
+///
+/// # Parameters
+/// - `time`: Resource containing time information.
+/// - `action_input`: The input actions of the current frame.
+/// - `terrain`: Relief levels of the loaded tiles.
+/// - `query`: Query for accessing the velocity and action of free moving users.
+///
+/// The velocity accelerates towards the pressed direction and slows down when released,
+/// the action only follows the velocity to pick the animation. Users stand still
+/// during the actions in place started by `handle_input`.
+pub fn handle_free_input(
+    time: Res<Time>,
+    action_input: Res<ActionInput>,
+    terrain: Terrain,
+    mut query: Query<FreeCharacterQuery, (With<IsUser>, Without<Dead>)>,
+) {
+    for (busy, mode, mut velocity, mut action, transform, offset) in query.iter_mut() {
+        if *mode != MovementMode::Free {
+            continue;
+        }
+        if busy.load(Ordering::Acquire) {
+            **velocity = Vec2::ZERO;
+            continue;
+        }
+
+        let is_running = action_input.pressed(InputAction::Run);
+        let tile_pos = player_nearest_tile_pos(transform, offset);
+        let speed = if is_running {
+            FREE_RUN_SPEED
+        } else {
+            FREE_WALK_SPEED
+        } / terrain.cost(&tile_pos);
+
+        let target = action_input.movement * speed;
+        let rate = if target == Vec2::ZERO {
+            FREE_DECELERATION
+        } else {
+            FREE_ACCELERATION
+        };
+        let step = rate * time.delta_seconds();
+        let difference = target - **velocity;
+        **velocity = if difference.length() <= step {
+            target
+        } else {
+            **velocity + difference.normalize() * step
+        };
+
+        *action = match action_input.direction() {
+            Some(direction) => {
+                let kind = if terrain.level(&tile_pos) == Some(WATER_LEVEL) {
+                    ActionKind::Swim
+                } else if is_running {
+                    ActionKind::Run
+                } else {
+                    ActionKind::Walk
+                };
+                Action::new(kind, direction)
+            }
+            None => Action::new(PLAYER_ACTION_DEFAULT.kind, action.direction.clone()),
+        };
+    }
+}
+
+/// Switches the movement mode of player characters.
+///
+/// # Parameters
+/// - `action_input`: The input actions of the current frame.
+/// - `query`: Query for accessing the movement mode and position of users.
+///
+/// Busy characters keep their mode until their action ends.
+/// Going back to tile steps snaps the character on the nearest tile.
+pub fn toggle_movement_mode(
+    action_input: Res<ActionInput>,
+    mut query: Query<MovementModeQuery, With<IsUser>>,
+) {
+    if !action_input.just_pressed(InputAction::ToggleMovement) {
+        return;
+    }
+
+    for (busy, mut mode, mut velocity, mut action, mut transform, offset) in query.iter_mut() {
+        if busy.load(Ordering::Acquire) {
+            continue;
         }
+
+        *mode = match *mode {
+            MovementMode::TileStep => MovementMode::Free,
+            MovementMode::Free => {
+                let tile_pos = player_nearest_tile_pos(&transform, offset);
+                let snapped = tile_pos.as_vec2() * TILE + **offset;
+                transform.translation = snapped.extend(transform.translation.z);
+                MovementMode::TileStep
+            }
+        };
+        **velocity = Vec2::ZERO;
+        action.kind = PLAYER_ACTION_DEFAULT.kind;
     }
 }
 
 /// Handles input for bot characters.
 ///
 /// # Parameters
+/// - `commands`: For spawning entities
 /// - `query`: Query for accessing and modifying the components related to bot actions.
-/// - `chunk_map`: Resource providing the game's chunk map.
-/// - `chunk_query`: Query for accessing tile storage data.
-/// - `tile_query`: Query for accessing relief level of tiles.
+/// - `terrain`: Relief levels of the loaded tiles.
+/// - `occupancy`: Tiles occupied by characters, blocked bots remember who blocked them.
+/// - `speech_events`: For sending what bots say.
+/// - `interactable_query`: Query to find the interactable entity faced by bots.
+/// - `interaction_events`: For sending the interactions of bots.
+/// - `item_events`: For sending what bots do with items.
+/// - `quest_events`: For sending the quests proposed by bots.
+/// - `asset_server`: For getting textures
+/// - `texture_atlas`: Registering / spawning textures
 ///
-/// This function processes the actions queued for bot characters and updates their actions accordingly.
+/// This function processes the commands queued for bot characters and updates their actions accordingly.
+/// Actions and `stop` cancel the follow mode, destination and attack target, `follow`, `goto`
+/// and `attack` replace each other. Attacks hit the faced tile after their hit delay.
+#[allow(clippy::too_many_arguments)]
 pub fn handle_bot_input(
+    mut commands: Commands,
     mut query: Query<BotCharacterQuery, With<IsBot>>,
-    chunk_map: Res<ChunkMap>,
-    chunk_query: Query<&TileStorage>,
-    tile_query: Query<&ReliefLevel>,
+    terrain: Terrain,
+    mut occupancy: ResMut<Occupancy>,
+    mut speech_events: EventWriter<BotSpeechEvent>,
+    interactable_query: Query<(), With<Interactable>>,
+    mut interaction_events: EventWriter<InteractionEvent>,
+    mut item_events: EventWriter<ItemActionEvent>,
+    mut quest_events: EventWriter<QuestProposalEvent>,
+    asset_server: Res<AssetServer>,
+    mut texture_atlas: ResMut<Assets<TextureAtlas>>,
 ) {
-    for (busy, mut action, mut timer, duration, transform, offset, agent) in query.iter_mut() {
+    for (entity, busy, mut action, mut timer, duration, transform, offset, queue) in
+        query.iter_mut()
+    {
         if busy.load(Ordering::Acquire) {
-            return;
+            continue;
         }
 
-        if let Ok(mut queue) = agent.action_queue.try_write() {
-            if let Some(new_action) = queue.pop_front() {
-                if is_action_possible(
-                    &new_action,
-                    transform,
-                    offset,
-                    &chunk_map,
-                    &chunk_query,
-                    &tile_query,
-                ) {
-                    *action = new_action;
-                    *timer = duration.generate_timer(&action);
-                    busy.store(true, Ordering::Release);
-                    return;
+        let command = queue.pop_front();
+        if matches!(
+            command,
+            Some(Command::Act(_) | Command::Stop | Command::GoTo(_) | Command::Attack(_))
+        ) {
+            commands.entity(entity).remove::<FollowTarget>();
+        }
+        if matches!(
+            command,
+            Some(Command::Act(_) | Command::Stop | Command::Follow(_) | Command::Attack(_))
+        ) {
+            commands.entity(entity).remove::<Destination>();
+        }
+        if matches!(
+            command,
+            Some(Command::Act(_) | Command::Stop | Command::Follow(_) | Command::GoTo(_))
+        ) {
+            commands.entity(entity).remove::<AttackTarget>();
+        }
+
+        let position = player_tile_pos(transform, offset);
+        match command {
+            Some(Command::Act(new_action)) => {
+                let Some((new_action, terrain_cost)) = terrain.plan_action(new_action, &position)
+                else {
+                    action.kind = PLAYER_ACTION_DEFAULT.kind;
+                    continue;
+                };
+                if let Err(blocker) = occupancy.reserve(entity, &new_action, &position) {
+                    commands.entity(entity).insert(BlockedBy(blocker));
+                    action.kind = PLAYER_ACTION_DEFAULT.kind;
+                    continue;
+                }
+                if new_action.kind.is_moving() {
+                    commands.entity(entity).remove::<BlockedBy>();
                 }
+                if new_action.kind == ActionKind::Type {
+                    spawn_effect(
+                        &mut commands,
+                        &TYPE_EFFECT_SPRITE_GRID,
+                        TYPE_EFFECT.clone(),
+                        transform.translation + BUBBLE_RELATIVE_POSITION,
+                        TYPE_EFFECT_DURATION,
+                        &asset_server.load(TYPE_EFFECT_SPRITE),
+                        &mut texture_atlas,
+                    );
+                }
+                if new_action.kind == ActionKind::Interact {
+                    send_interaction(
+                        entity,
+                        &new_action,
+                        &position,
+                        &occupancy,
+                        &interactable_query,
+                        &mut interaction_events,
+                    );
+                }
+                if new_action.kind == ActionKind::Attack {
+                    commands.spawn(Hitbox::new(
+                        entity,
+                        &new_action,
+                        &position,
+                        PLAYER_ATTACK_DAMAGE,
+                        duration.hit_delay(),
+                    ));
+                }
+                *action = new_action;
+                *timer = duration.generate_timer(&action, terrain_cost);
+                busy.store(true, Ordering::Release);
+                continue;
+            }
+            Some(Command::SayTo { target, message }) => speech_events.send(BotSpeechEvent {
+                speaker: entity,
+                target,
+                message,
+            }),
+            Some(Command::Item(item_action)) => item_events.send(ItemActionEvent {
+                actor: entity,
+                action: item_action,
+            }),
+            Some(Command::Follow(follow)) => {
+                commands.entity(entity).insert(follow);
+            }
+            Some(Command::GoTo(relative_position)) => {
+                let destination = position + relative_position;
+                commands.entity(entity).insert(Destination(destination));
+            }
+            Some(Command::Attack(attack)) => {
+                commands.entity(entity).insert(attack);
+            }
+            Some(Command::ProposeQuest(proposal)) => quest_events.send(QuestProposalEvent {
+                giver: entity,
+                proposal,
+            }),
+            Some(Command::Wait(seconds)) => {
+                *action = Action::new(PLAYER_ACTION_DEFAULT.kind, action.direction.clone());
+                *timer = ActionTimer(Timer::from_seconds(seconds, TimerMode::Once));
+                busy.store(true, Ordering::Release);
+                continue;
             }
+            _ => {}
         }
         action.kind = PLAYER_ACTION_DEFAULT.kind;
     }
 }
 
-/// Determines if an action is possible based on the current game state.
-///
-/// # Parameters
-/// - `action`: The action to be evaluated.
-/// - `transform`: The current transform of the entity.
-/// - `offset`: Tileset offset for calculating positions.
-/// - `chunk_map`: Resource providing the game's chunk map.
-/// - `chunk_query`: Query for accessing tile storage data.
-/// - `tile_query`: Query for accessing relief level of tiles.
-///
-/// # Returns
-/// Returns `true` if the action is possible, `false` otherwise.
-///
-/// This function checks if the specified action can be performed by the entity based on its current state and the state of the game world.
-fn is_action_possible(
+/// Sends an interaction with the interactable entity on the tile faced by a character.
+fn send_interaction(
+    entity: Entity,
     action: &Action,
-    transform: &Transform,
-    offset: &TilesetOffset,
-    chunk_map: &Res<ChunkMap>,
-    chunk_query: &Query<&TileStorage>,
-    tile_query: &Query<&ReliefLevel>,
-) -> bool {
-    if !matches!(action.kind, ActionKind::Walk | ActionKind::Run) {
-        return true;
-    }
-    let position = &player_tile_pos(transform, offset);
-    let position_relative = relative_tile_pos(position);
-
-    let target_pos = *position + action.get_raw_transformation();
-    let target_chunk_pos = tile_pos_to_chunk_pos(&target_pos);
-
-    let current_chunk_pos = tile_pos_to_chunk_pos(position);
-    let (layer, _) = chunk_map
-        .get(&current_chunk_pos)
-        .expect("Unable to get level 0 layer!");
-    let tile_storage = chunk_query
-        .get(*layer)
-        .expect("Unable to get level 0 tile storage!");
-    let tile_entiy = tile_storage
-        .get(&position_relative)
-        .expect("Unable to get level 0 tile entity!");
-    let level0 = **tile_query
-        .get(tile_entiy)
-        .expect("Unable to get level 0 value!") as f32;
-
-    if let Some((layer, _)) = chunk_map.get(&target_chunk_pos) {
-        let tile_storage = chunk_query
-            .get(*layer)
-            .expect("Unable to get level 1 tile storage!");
-        let target_relative = relative_tile_pos(&target_pos);
-        if let Some(tile_entiy) = tile_storage.get(&target_relative) {
-            let level1 = **tile_query
-                .get(tile_entiy)
-                .expect("Unable to get level 1 value!") as f32;
-            return level0 == level1;
-        } else {
-            return true;
-        }
+    position: &IVec2,
+    occupancy: &Occupancy,
+    interactable_query: &Query<(), With<Interactable>>,
+    interaction_events: &mut EventWriter<InteractionEvent>,
+) {
+    let faced_pos = *position + action.direction.to_vector();
+    if let Some(target) = occupancy
+        .blocker(&faced_pos, entity)
+        .filter(|target| interactable_query.contains(*target))
+    {
+        interaction_events.send(InteractionEvent {
+            interactor: entity,
+            target,
+        });
     }
-    false
 }
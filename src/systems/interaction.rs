@@ -0,0 +1,106 @@
+use std::sync::atomic::Ordering;
+
+use bevy::prelude::*;
+
+use crate::components::action::{Action, ActionDirection, ActionKind};
+use crate::components::character::Busy;
+use crate::components::display::SpeechBubble;
+use crate::components::gpt::BotSpeechEvent;
+use crate::components::interaction::{Interactable, InteractionEvent};
+use crate::components::item::Inventory;
+use crate::components::texture::TilesetOffset;
+use crate::constants::interaction::*;
+use crate::constants::item::CHEST_LOOT;
+use crate::util::effect::spawn_speech;
+use crate::util::position::player_tile_pos;
+
+// Define a type for characters turning to face who talks to them
+type FacingCharacterQuery<'a> = (&'a Busy, &'a mut Action, &'a Transform, &'a TilesetOffset);
+
+/// Resolves the interactions sent by characters.
+///
+/// Signs show their text and chests open once, both above themselves.
+/// Opening a chest gives its loot, a chest stays closed while the loot does not fit.
+/// Pals are greeted by the interacting character and turn to face it when idle.
+///
+/// # Parameters
+/// - `commands`: Commands for spawning and replacing text bubbles.
+/// - `interaction_events`: Interactions sent by characters.
+/// - `speech_events`: For greeting the Pals.
+/// - `interactable_query`: Query to access and modify the interactable entities.
+/// - `character_query`: Query to access the position and action of characters.
+/// - `inventory_query`: Query to access the inventories of characters.
+/// - `speech_query`: Query to access the speech bubbles already displayed.
+pub fn handle_interactions(
+    mut commands: Commands,
+    mut interaction_events: EventReader<InteractionEvent>,
+    mut speech_events: EventWriter<BotSpeechEvent>,
+    mut interactable_query: Query<(&mut Interactable, Option<&mut Sprite>, &Name)>,
+    mut character_query: Query<FacingCharacterQuery>,
+    mut inventory_query: Query<&mut Inventory>,
+    speech_query: Query<(Entity, &Parent), With<SpeechBubble>>,
+) {
+    for event in interaction_events.read() {
+        let Ok((mut interactable, sprite, name)) = interactable_query.get_mut(event.target) else {
+            continue;
+        };
+
+        let text = match interactable.as_mut() {
+            Interactable::Sign(text) => text.clone(),
+            Interactable::Chest { is_open: true } => CHEST_EMPTY_TEXT.to_string(),
+            Interactable::Chest { is_open } => {
+                let Ok(mut inventory) = inventory_query.get_mut(event.interactor) else {
+                    continue;
+                };
+                let mut looted_inventory = inventory.clone();
+                if looted_inventory.add(CHEST_LOOT.id, CHEST_LOOT.count) > 0 {
+                    CHEST_FULL_TEXT.to_string()
+                } else {
+                    *inventory = looted_inventory;
+                    *is_open = true;
+                    if let Some(mut sprite) = sprite {
+                        sprite.color = CHEST_OPEN_COLOR;
+                    }
+                    format!("{} Found {}.", CHEST_OPEN_TEXT, CHEST_LOOT.describe())
+                }
+            }
+            Interactable::Talk => {
+                speech_events.send(BotSpeechEvent {
+                    speaker: event.interactor,
+                    target: name.to_string(),
+                    message: TALK_GREETING.to_string(),
+                });
+                face_interactor(&mut character_query, event);
+                continue;
+            }
+        };
+
+        // Replace the previous text bubble
+        for (bubble, parent) in speech_query.iter() {
+            if parent.get() == event.target {
+                commands.entity(bubble).despawn_recursive();
+            }
+        }
+        spawn_speech(&mut commands, event.target, text);
+    }
+}
+
+/// Turns an idle interaction target toward the interacting character.
+fn face_interactor(character_query: &mut Query<FacingCharacterQuery>, event: &InteractionEvent) {
+    let Ok((_, _, transform, offset)) = character_query.get(event.interactor) else {
+        return;
+    };
+    let interactor_pos = player_tile_pos(transform, offset);
+
+    let Ok((busy, mut action, transform, offset)) = character_query.get_mut(event.target) else {
+        return;
+    };
+    if busy.load(Ordering::Acquire) {
+        return;
+    }
+    if let Some(direction) =
+        ActionDirection::from_vector(interactor_pos - player_tile_pos(transform, offset))
+    {
+        *action = Action::new(ActionKind::Stand, direction);
+    }
+}
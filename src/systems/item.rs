@@ -0,0 +1,198 @@
+use bevy::log;
+use bevy::prelude::*;
+
+use crate::bundles::item::ItemDropBundle;
+use crate::components::action::Action;
+use crate::components::character::{Dead, Health, HealthChangeCause, HealthChangeEvent, MaxHealth};
+use crate::components::item::{Inventory, ItemAction, ItemActionEvent, ItemDrop, ItemStack};
+use crate::components::map::Occupancy;
+use crate::components::texture::TilesetOffset;
+use crate::constants::item::*;
+use crate::util::position::{pixel_pos_to_tile_pos, player_nearest_tile_pos, player_tile_pos};
+
+// Define a type for characters carrying items
+type CarrierQuery<'a> = (
+    Entity,
+    &'a Name,
+    &'a Transform,
+    &'a TilesetOffset,
+    &'a Action,
+    &'a mut Inventory,
+);
+
+/// Resolves what characters do with items.
+///
+/// # Parameters
+/// - `commands`: Commands for spawning and despawning item drops.
+/// - `item_events`: Item actions sent by characters.
+/// - `health_events`: For healing characters using items.
+/// - `occupancy`: Tiles occupied by characters, to find the faced character.
+/// - `asset_server`: For getting the item textures.
+/// - `texture_atlas`: Registering the item textures.
+/// - `carrier_query`: Query to access characters and their inventories.
+/// - `drop_query`: Query to access the items lying in the world.
+/// - `health_query`: Query to access the health of characters.
+///
+/// Items that do not fit in an inventory stay where they were, and healing items are kept
+/// at full health. Dead characters cannot handle items.
+#[allow(clippy::too_many_arguments)]
+pub fn handle_item_actions(
+    mut commands: Commands,
+    mut item_events: EventReader<ItemActionEvent>,
+    mut health_events: EventWriter<HealthChangeEvent>,
+    occupancy: Res<Occupancy>,
+    asset_server: Res<AssetServer>,
+    mut texture_atlas: ResMut<Assets<TextureAtlas>>,
+    mut carrier_query: Query<CarrierQuery, Without<Dead>>,
+    mut drop_query: Query<(Entity, &Transform, &mut ItemDrop)>,
+    health_query: Query<(&Health, &MaxHealth)>,
+) {
+    for event in item_events.read() {
+        let Ok((actor, actor_name, transform, offset, action, _)) = carrier_query.get(event.actor)
+        else {
+            continue;
+        };
+        let actor_name = actor_name.to_string();
+        // Free moving actors are between tiles
+        let tile_pos = player_nearest_tile_pos(transform, offset);
+        let faced_pos = tile_pos + action.direction.to_vector();
+
+        match &event.action {
+            ItemAction::PickUp => {
+                let Ok((.., mut inventory)) = carrier_query.get_mut(actor) else {
+                    continue;
+                };
+                for (drop_entity, drop_transform, mut item_drop) in drop_query.iter_mut() {
+                    let drop_pos = pixel_pos_to_tile_pos(&drop_transform.translation.xy());
+                    if drop_pos != tile_pos && drop_pos != faced_pos {
+                        continue;
+                    }
+
+                    let left = inventory.add(item_drop.id, item_drop.count);
+                    if left < item_drop.count {
+                        log::info!("{} picks up {}", actor_name, item_drop.describe());
+                    }
+                    item_drop.count = left;
+                    if left == 0 {
+                        commands.entity(drop_entity).despawn_recursive();
+                    }
+                }
+            }
+            ItemAction::Drop { item, count } => {
+                let Ok((.., mut inventory)) = carrier_query.get_mut(actor) else {
+                    continue;
+                };
+                let Some(id) = carried_item(&inventory, item.as_deref()) else {
+                    continue;
+                };
+                let removed = inventory.remove(id, *count);
+                if removed == 0 {
+                    continue;
+                }
+
+                let stack = ItemStack::new(id, removed);
+                log::info!("{} drops {}", actor_name, stack.describe());
+                commands.spawn(ItemDropBundle::new(
+                    stack,
+                    tile_pos,
+                    &asset_server.load(ITEM_SPRITE),
+                    &mut texture_atlas,
+                ));
+            }
+            ItemAction::Give {
+                item,
+                count,
+                target,
+            } => {
+                let receiver = match target {
+                    Some(target) => carrier_query
+                        .iter()
+                        .find(|(other, name, ..)| {
+                            *other != actor && name.as_str().eq_ignore_ascii_case(target)
+                        })
+                        .map(|(other, _, transform, offset, ..)| {
+                            (other, player_tile_pos(transform, offset))
+                        }),
+                    None => occupancy
+                        .blocker(&faced_pos, actor)
+                        .filter(|other| carrier_query.contains(*other))
+                        .map(|other| (other, faced_pos)),
+                };
+                let Some((receiver, receiver_pos)) = receiver else {
+                    continue;
+                };
+                if (receiver_pos - tile_pos).abs().max_element() > ITEM_GIVE_DISTANCE {
+                    continue;
+                }
+
+                let Ok([(.., mut inventory), (_, receiver_name, .., mut receiver_inventory)]) =
+                    carrier_query.get_many_mut([actor, receiver])
+                else {
+                    continue;
+                };
+                let Some(id) = carried_item(&inventory, item.as_deref()) else {
+                    continue;
+                };
+                let removed = inventory.remove(id, *count);
+                let left = receiver_inventory.add(id, removed);
+                inventory.add(id, left);
+
+                if removed > left {
+                    let stack = ItemStack::new(id, removed - left);
+                    log::info!(
+                        "{} gives {} to {}",
+                        actor_name,
+                        stack.describe(),
+                        receiver_name
+                    );
+                }
+            }
+            ItemAction::Use { item } => {
+                if health_query
+                    .get(actor)
+                    .map_or(true, |(health, max_health)| **health >= **max_health)
+                {
+                    continue;
+                }
+                let Ok((.., mut inventory)) = carrier_query.get_mut(actor) else {
+                    continue;
+                };
+                let definition = match item {
+                    Some(name) => ITEM_REGISTRY
+                        .find(name)
+                        .filter(|definition| inventory.count(definition.id) > 0),
+                    None => inventory
+                        .stacks()
+                        .iter()
+                        .filter_map(|stack| ITEM_REGISTRY.get(stack.id))
+                        .find(|definition| definition.healing > 0),
+                };
+                let Some(definition) = definition.filter(|definition| definition.healing > 0)
+                else {
+                    continue;
+                };
+                if inventory.remove(definition.id, 1) == 0 {
+                    continue;
+                }
+
+                log::info!("{} uses {}", actor_name, definition.name);
+                health_events.send(HealthChangeEvent {
+                    entity: actor,
+                    amount: definition.healing as i32,
+                    cause: HealthChangeCause::Item(definition.id),
+                });
+            }
+        }
+    }
+}
+
+/// Finds the id of a carried item, the first one when not given.
+fn carried_item(inventory: &Inventory, item: Option<&str>) -> Option<&'static str> {
+    match item {
+        Some(name) => ITEM_REGISTRY
+            .find(name)
+            .map(|definition| definition.id)
+            .filter(|id| inventory.count(id) > 0),
+        None => inventory.first(),
+    }
+}
@@ -0,0 +1,91 @@
+use bevy::prelude::*;
+
+use crate::components::character::{IsBot, IsUser};
+use crate::components::exploration::ExploredMap;
+use crate::components::input::{ActionInput, InputAction};
+use crate::components::minimap::*;
+use crate::components::structure::PointsOfInterest;
+use crate::components::texture::TilesetOffset;
+use crate::util::minimap::draw_map;
+use crate::util::position::player_tile_pos;
+
+// Define a type for the characters shown on the maps
+type MarkedCharacterQuery<'a> = (&'a Transform, &'a TilesetOffset, Has<IsUser>);
+
+// Define a filter matching users and bots
+type IsCharacter = Or<(With<IsUser>, With<IsBot>)>;
+
+// Define a type for the center and markers of drawn maps
+type DrawnMap = (IVec2, Vec<(IVec2, MapMarker)>);
+
+/// Draws the minimap around the user, and the world map while it is shown.
+///
+/// Only the explored tiles are drawn. The world map is toggled by the
+/// `WorldMap` input action. The images are only redrawn when the explored
+/// tiles or the markers changed, as every redraw uploads them again.
+///
+/// # Parameters
+/// - `action_input`: The input actions of the current frame.
+/// - `images`: The image assets, holding the map images.
+/// - `explored`: Resource remembering the explored tiles.
+/// - `points_of_interest`: Resource registering the named structures.
+/// - `minimap_query`: Query to access the minimap image.
+/// - `world_map_query`: Query to access and toggle the world map image.
+/// - `character_query`: Query to access the positions of users and bots.
+/// - `last_drawn`: The center and markers of the last drawn maps.
+#[allow(clippy::too_many_arguments)]
+pub fn update_maps(
+    action_input: Res<ActionInput>,
+    mut images: ResMut<Assets<Image>>,
+    explored: Res<ExploredMap>,
+    points_of_interest: Res<PointsOfInterest>,
+    minimap_query: Query<&UiImage, With<Minimap>>,
+    mut world_map_query: Query<(&UiImage, &mut Visibility), With<WorldMap>>,
+    character_query: Query<MarkedCharacterQuery, IsCharacter>,
+    mut last_drawn: Local<Option<DrawnMap>>,
+) {
+    let mut center = IVec2::ZERO;
+    let mut markers = points_of_interest
+        .values()
+        .map(|point| (point.center, MapMarker::Place))
+        .collect::<Vec<_>>();
+    for (transform, offset, is_user) in character_query.iter() {
+        let tile_pos = player_tile_pos(transform, offset);
+        if is_user {
+            center = tile_pos;
+            markers.push((tile_pos, MapMarker::User));
+        } else {
+            markers.push((tile_pos, MapMarker::Bot));
+        }
+    }
+
+    let drawn = (center, markers);
+    let is_changed = explored.is_changed() || last_drawn.as_ref() != Some(&drawn);
+    let (center, markers) = last_drawn.insert(drawn);
+    let (center, markers) = (*center, markers.as_slice());
+
+    if is_changed {
+        for minimap in minimap_query.iter() {
+            if let Some(image) = images.get_mut(&minimap.texture) {
+                draw_map(image, &explored, center, markers);
+            }
+        }
+    }
+
+    for (world_map, mut visibility) in world_map_query.iter_mut() {
+        let is_toggled = action_input.just_pressed(InputAction::WorldMap);
+        if is_toggled {
+            *visibility = match *visibility {
+                Visibility::Hidden => Visibility::Inherited,
+                _ => Visibility::Hidden,
+            };
+        }
+        // The world map is not kept up to date while hidden
+        if *visibility == Visibility::Hidden || !(is_changed || is_toggled) {
+            continue;
+        }
+        if let Some(image) = images.get_mut(&world_map.texture) {
+            draw_map(image, &explored, center, markers);
+        }
+    }
+}
@@ -6,9 +6,33 @@ pub mod animation;
 pub mod bot;
 // Deals with chunk loading and despawning.
 pub mod chunk;
+// Advances the in-game time and its lighting.
+pub mod clock;
+// Resolves attacks and knockbacks.
+pub mod combat;
+// Manages temporary display elements.
+pub mod display;
+// Tracks, shows and saves the explored tiles.
+pub mod exploration;
+// Applies damage, healing, death and respawn.
+pub mod health;
 // Handles user & bot input and interactions.
 pub mod input;
+// Resolves the interactions with objects and characters.
+pub mod interaction;
+// Moves items between inventories and the world.
+pub mod item;
+// Remembers the explored chunks and draws the maps.
+pub mod minimap;
 // Implements character and camera movement.
 pub mod movement;
+// Validates, tracks and shows the quests.
+pub mod quest;
 // Manages the setup and initialization of the game.
 pub mod setup;
+// Stamps the generated structures on the chunks.
+pub mod structure;
+// Changes the weather and shows its effects.
+pub mod weather;
+// Spawns, drives and despawns the wildlife creatures.
+pub mod wildlife;
@@ -4,41 +4,204 @@ use bevy::prelude::*;
 
 use crate::components::action::*;
 use crate::components::character::*;
-use crate::constants::action::ACTION_TICK_FREQUENCY;
+use crate::components::interaction::Interactable;
+use crate::components::map::{Occupancy, Terrain};
+use crate::components::structure::StructurePiece;
+use crate::components::texture::TilesetOffset;
+use crate::components::weather::Weather;
+use crate::constants::action::MOVEMENT_EASING;
+use crate::constants::character::USER_HITBOX;
+use crate::util::animation::ease_movement;
+use crate::util::position::{
+    action_start_tile_pos, pixel_pos_to_nearest_tile_pos, pixel_pos_to_tile_pos, player_tile_pos,
+};
+
+// Define a type for character position queries
+type CharacterPositionQuery<'a> = (
+    Entity,
+    &'a Transform,
+    &'a TilesetOffset,
+    &'a Busy,
+    &'a Action,
+    &'a ActionTimer,
+    &'a Motion,
+);
+
+// Define a filter matching interactable objects and structures, characters excluded
+type IsObject = (
+    Or<(With<Interactable>, With<StructurePiece>)>,
+    Without<Busy>,
+);
 
 /// Updates the position of characters based on their actions.
 ///
-/// This system moves characters each frame based on their current actions
+/// This system runs in the fixed-timestep schedule. It computes the position of
+/// characters from the start and end of their current action and the elapsed time,
 /// and updates their busy status when actions are completed.
+/// The transform is only written at the end of the action, see `interpolate_motion`.
+/// Moves last longer when the weather slows movement down.
 ///
 /// # Parameters
-/// - `query`: Query for accessing and modifying character transforms, busy status, and actions.
-pub fn move_characters(mut query: Query<(&mut Transform, &mut Busy, &Action, &mut ActionTimer)>) {
-    for (mut transform, busy, action, mut timer) in query.iter_mut() {
+/// - `time`: Resource containing the fixed time step.
+/// - `weather`: Resource holding the current weather.
+/// - `query`: Query for accessing and modifying character transforms, busy status, actions and motions.
+pub fn move_characters(
+    time: Res<Time>,
+    weather: Res<Weather>,
+    mut query: Query<(
+        &mut Transform,
+        &Busy,
+        &Action,
+        &mut ActionTimer,
+        &mut Motion,
+    )>,
+) {
+    for (mut transform, busy, action, mut timer, mut motion) in query.iter_mut() {
         if !busy.load(Ordering::Acquire) {
             continue;
         }
 
-        let transformation = action.get_transformation();
+        if timer.elapsed().is_zero() {
+            motion.start = transform.translation;
+            motion.current = transform.translation;
+        }
+        if action.kind.is_moving() {
+            timer.tick(
+                time.delta()
+                    .mul_f32(weather.kind.definition().movement_factor),
+            );
+        } else {
+            timer.tick(time.delta());
+        }
+        motion.previous = motion.current;
 
-        // Don't move if the animation is not a moving animation
-        // Shouldn't impact performances, but can be replaced by a component in the action bundle
-        if transformation == Vec3::new(0., 0., 0.) {
+        let end = motion.start + action.get_transformation();
+        if timer.finished() {
+            motion.current = end.round();
+            motion.previous = motion.current;
+            transform.translation = motion.current;
+            busy.store(false, Ordering::Release);
             continue;
         }
 
-        timer.tick(ACTION_TICK_FREQUENCY);
+        let progress = ease_movement(timer.percent(), MOVEMENT_EASING);
+        motion.current = motion.start.lerp(end, progress);
+    }
+}
 
-        if timer.finished() {
-            busy.store(false, Ordering::Release);
-            transform.translation = transform.translation.round();
-            return;
+/// Interpolates the rendered position of characters between two fixed steps.
+///
+/// # Parameters
+/// - `fixed_time`: Resource containing the fixed time step and its overstep.
+/// - `query`: Query for accessing and modifying character transforms and motions.
+pub fn interpolate_motion(
+    fixed_time: Res<Time<Fixed>>,
+    mut query: Query<(&mut Transform, &Busy, &ActionTimer, &Motion)>,
+) {
+    let alpha = fixed_time.overstep_percentage();
+    for (mut transform, busy, timer, motion) in query.iter_mut() {
+        // Actions without a fixed step yet have no motion to show
+        if !busy.load(Ordering::Acquire) || timer.elapsed().is_zero() {
+            continue;
         }
+        transform.translation = motion.previous.lerp(motion.current, alpha);
+    }
+}
 
-        let movement = transformation
-            * Vec3::splat(ACTION_TICK_FREQUENCY.as_secs_f32() / timer.duration().as_secs_f32());
+/// Moves the characters in free movement mode according to their velocity.
+///
+/// The collision box cannot overlap a tile the character could not step on from
+/// its current tile, nor a tile occupied by another character. Each axis is resolved
+/// on its own so characters slide along the obstacles.
+///
+/// # Parameters
+/// - `time`: Resource containing time information.
+/// - `weather`: Resource holding the current weather, slowing movement down.
+/// - `terrain`: Relief levels of the loaded tiles.
+/// - `occupancy`: Tiles occupied by characters.
+/// - `query`: Query for accessing and modifying free moving character transforms and velocities.
+pub fn move_free_characters(
+    time: Res<Time>,
+    weather: Res<Weather>,
+    terrain: Terrain,
+    occupancy: Res<Occupancy>,
+    mut query: Query<(
+        Entity,
+        &mut Transform,
+        &TilesetOffset,
+        &MovementMode,
+        &mut Velocity,
+    )>,
+) {
+    for (entity, mut transform, offset, mode, mut velocity) in query.iter_mut() {
+        if *mode != MovementMode::Free || **velocity == Vec2::ZERO {
+            continue;
+        }
 
-        transform.translation += movement;
+        let position = transform.translation.xy() - **offset;
+        let tile_pos = pixel_pos_to_nearest_tile_pos(&position);
+        let can_overlap = |center: Vec2| {
+            [
+                center - USER_HITBOX,
+                center + USER_HITBOX,
+                center + Vec2::new(USER_HITBOX.x, -USER_HITBOX.y),
+                center + Vec2::new(-USER_HITBOX.x, USER_HITBOX.y),
+            ]
+            .iter()
+            .map(pixel_pos_to_nearest_tile_pos)
+            .all(|corner_pos| {
+                terrain.can_enter(&tile_pos, &corner_pos)
+                    && (corner_pos == tile_pos || occupancy.blocker(&corner_pos, entity).is_none())
+            })
+        };
+
+        let movement =
+            **velocity * weather.kind.definition().movement_factor * time.delta_seconds();
+        let mut new_position = position;
+        for axis in [Vec2::X, Vec2::Y] {
+            let candidate = new_position + movement * axis;
+            if can_overlap(candidate) {
+                new_position = candidate;
+            } else {
+                **velocity *= Vec2::ONE - axis;
+            }
+        }
+        transform.translation += (new_position - position).extend(0.);
+    }
+}
+
+/// Updates the tiles occupied by characters and interactable objects.
+///
+/// Each character occupies the tile its action started from and, while moving,
+/// the tile it goes to. Finished moves release their starting tile.
+/// Interactable objects and structure pieces occupy the tile they lie on, dead
+/// characters nothing.
+///
+/// # Parameters
+/// - `occupancy`: Resource tracking the occupied tiles.
+/// - `query`: Query to access the position and action of characters.
+/// - `object_query`: Query to access the position of interactable objects and structures.
+pub fn update_occupancy(
+    mut occupancy: ResMut<Occupancy>,
+    query: Query<CharacterPositionQuery, Without<Dead>>,
+    object_query: Query<(Entity, &Transform), IsObject>,
+) {
+    occupancy.clear();
+    for (entity, transform) in object_query.iter() {
+        occupancy.occupy(pixel_pos_to_tile_pos(&transform.translation.xy()), entity);
+    }
+    for (entity, transform, offset, busy, action, timer, motion) in query.iter() {
+        let is_moving = busy.load(Ordering::Acquire) && action.kind.is_moving();
+        let start_pos = if is_moving {
+            action_start_tile_pos(transform, offset, motion, timer)
+        } else {
+            player_tile_pos(transform, offset)
+        };
+
+        occupancy.occupy(start_pos, entity);
+        if is_moving {
+            occupancy.occupy(start_pos + action.get_raw_transformation(), entity);
+        }
     }
 }
 
@@ -0,0 +1,299 @@
+use bevy::log;
+use bevy::prelude::*;
+
+use crate::components::character::IsUser;
+use crate::components::display::{QuestLogPanel, QuestLogText};
+use crate::components::gpt::GPTAgent;
+use crate::components::input::{ActionInput, InputAction};
+use crate::components::interaction::{Interactable, InteractionEvent};
+use crate::components::item::{Inventory, ItemDrop};
+use crate::components::map::Terrain;
+use crate::components::quest::*;
+use crate::components::texture::TilesetOffset;
+use crate::constants::generation::WATER_LEVEL;
+use crate::constants::item::CHEST_LOOT;
+use crate::constants::quest::*;
+use crate::util::effect::spawn_speech;
+use crate::util::position::player_tile_pos;
+
+// Define a type for bots giving quests
+type GiverQuery<'a> = (
+    &'a Name,
+    &'a Transform,
+    &'a TilesetOffset,
+    Option<&'a GPTAgent>,
+);
+
+/// Validates the quests proposed by bots against the world.
+///
+/// Tiles to reach must be generated, dry and close to the giver, items to
+/// collect must exist in the world in the asked number, and characters to
+/// talk to must exist. The giver is told whether its quest was accepted.
+///
+/// # Parameters
+/// - `commands`: Commands for spawning speech bubbles.
+/// - `quest_events`: The quests proposed by bots.
+/// - `quest_log`: Resource holding the quests of the user.
+/// - `terrain`: Relief levels of the loaded tiles.
+/// - `giver_query`: Query to access the bots proposing quests.
+/// - `interactable_query`: Query to access the characters to talk to and the chests.
+/// - `drop_query`: Query to access the items lying in the world.
+/// - `inventory_query`: Query to access the carried items.
+#[allow(clippy::too_many_arguments)]
+pub fn validate_quests(
+    mut commands: Commands,
+    mut quest_events: EventReader<QuestProposalEvent>,
+    mut quest_log: ResMut<QuestLog>,
+    terrain: Terrain,
+    giver_query: Query<GiverQuery>,
+    interactable_query: Query<(&Interactable, Option<&Name>)>,
+    drop_query: Query<&ItemDrop>,
+    inventory_query: Query<&Inventory>,
+) {
+    for event in quest_events.read() {
+        let Ok((name, transform, offset, agent)) = giver_query.get(event.giver) else {
+            continue;
+        };
+        let giver_pos = player_tile_pos(transform, offset);
+
+        let validate = |objective: &Objective| match objective {
+            Objective::Reach(relative_position) => {
+                let tile_pos = giver_pos + *relative_position;
+                if relative_position.abs().max_element() > QUEST_MAX_DISTANCE {
+                    return Err(format!(
+                        "the tile to reach is more than {} tiles away",
+                        QUEST_MAX_DISTANCE
+                    ));
+                }
+                match terrain.level(&tile_pos) {
+                    None => Err("the tile to reach is unknown".to_string()),
+                    Some(WATER_LEVEL) => Err("the tile to reach is water".to_string()),
+                    Some(_) => Ok(Objective::Reach(tile_pos)),
+                }
+            }
+            Objective::Collect { item, count } => {
+                let dropped: u32 = drop_query
+                    .iter()
+                    .filter(|item_drop| item_drop.id == item)
+                    .map(|item_drop| item_drop.count)
+                    .sum();
+                let carried: u32 = inventory_query
+                    .iter()
+                    .map(|inventory| inventory.count(item))
+                    .sum();
+                let in_chests: u32 = interactable_query
+                    .iter()
+                    .filter(|(interactable, _)| {
+                        **interactable == Interactable::Chest { is_open: false }
+                            && CHEST_LOOT.id == item
+                    })
+                    .map(|_| CHEST_LOOT.count)
+                    .sum();
+                let available = dropped + carried + in_chests;
+                if available < *count {
+                    Err(format!(
+                        "there are only {} {} in the world",
+                        available, item
+                    ))
+                } else {
+                    Ok(objective.clone())
+                }
+            }
+            Objective::TalkTo(target) => {
+                let exists = interactable_query.iter().any(|(interactable, name)| {
+                    *interactable == Interactable::Talk
+                        && name.is_some_and(|name| name.as_str().eq_ignore_ascii_case(target))
+                });
+                if exists {
+                    Ok(objective.clone())
+                } else {
+                    Err(format!("there is nobody called {} to talk to", target))
+                }
+            }
+        };
+
+        let title = &event.proposal.title;
+        let objectives = if quest_log.len() >= QUEST_MAX_ACTIVE {
+            Err(format!("the user already has {} quests", QUEST_MAX_ACTIVE))
+        } else {
+            event
+                .proposal
+                .objectives
+                .iter()
+                .map(validate)
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        match objectives {
+            Ok(objectives) => {
+                log::info!("{} proposes the quest \"{}\"", name, title);
+                if let Some(agent) = agent {
+                    agent.add_context(&format!("Your quest \"{}\" was accepted.", title));
+                }
+                spawn_speech(
+                    &mut commands,
+                    event.giver,
+                    format!("{} {}", QUEST_PROPOSED_TEXT, title),
+                );
+                quest_log.push(Quest {
+                    title: title.clone(),
+                    giver: event.giver,
+                    objectives: objectives
+                        .into_iter()
+                        .map(|objective| QuestObjective {
+                            objective,
+                            is_done: false,
+                        })
+                        .collect(),
+                });
+            }
+            Err(reason) => {
+                log::info!("{} proposes an invalid quest: {}", name, reason);
+                if let Some(agent) = agent {
+                    agent.add_context(&format!(
+                        "Your quest \"{}\" was rejected: {}.",
+                        title, reason
+                    ));
+                }
+            }
+        }
+    }
+}
+
+/// Fulfils the objectives of the user quests, and completes the quests.
+///
+/// Objectives stay fulfilled, a completed quest is removed from the log
+/// and its giver congratulates the user.
+///
+/// # Parameters
+/// - `commands`: Commands for spawning speech bubbles.
+/// - `quest_log`: Resource holding the quests of the user.
+/// - `interaction_events`: Interactions sent by characters, talking to characters.
+/// - `user_query`: Query to access the user position and inventory.
+/// - `name_query`: Query to access the names of the interaction targets.
+/// - `agent_query`: Query to access the agents of the quest givers.
+pub fn track_quests(
+    mut commands: Commands,
+    mut quest_log: ResMut<QuestLog>,
+    mut interaction_events: EventReader<InteractionEvent>,
+    user_query: Query<(Entity, &Transform, &TilesetOffset, &Inventory), With<IsUser>>,
+    name_query: Query<&Name>,
+    agent_query: Query<&GPTAgent>,
+) {
+    let Ok((user, transform, offset, inventory)) = user_query.get_single() else {
+        return;
+    };
+    let user_pos = player_tile_pos(transform, offset);
+    let talked_to = interaction_events
+        .read()
+        .filter(|event| event.interactor == user)
+        .filter_map(|event| name_query.get(event.target).ok())
+        .collect::<Vec<_>>();
+
+    let is_fulfilled = |objective: &Objective| match objective {
+        Objective::Reach(tile_pos) => {
+            (*tile_pos - user_pos).abs().max_element() <= QUEST_REACH_DISTANCE
+        }
+        Objective::Collect { item, count } => inventory.count(item) >= *count,
+        Objective::TalkTo(target) => talked_to
+            .iter()
+            .any(|name| name.as_str().eq_ignore_ascii_case(target)),
+    };
+
+    // Only modify the log when something changed, the quest log UI follows it
+    let has_progress = quest_log.iter().any(|quest| {
+        quest
+            .objectives
+            .iter()
+            .any(|objective| !objective.is_done && is_fulfilled(&objective.objective))
+    });
+    if !has_progress {
+        return;
+    }
+
+    for quest in quest_log.iter_mut() {
+        for objective in quest.objectives.iter_mut() {
+            objective.is_done |= is_fulfilled(&objective.objective);
+        }
+    }
+    quest_log.retain(|quest| {
+        if !quest.is_complete() {
+            return true;
+        }
+        log::info!("The quest \"{}\" is completed", quest.title);
+        if let Ok(agent) = agent_query.get(quest.giver) {
+            agent.add_context(&format!(
+                "The user completed your quest \"{}\".",
+                quest.title
+            ));
+        }
+        if commands.get_entity(quest.giver).is_some() {
+            spawn_speech(
+                &mut commands,
+                quest.giver,
+                format!("{} {}!", QUEST_COMPLETED_TEXT, quest.title),
+            );
+        }
+        false
+    });
+}
+
+/// Shows or hides the quest log, and keeps it up to date.
+///
+/// # Parameters
+/// - `action_input`: The input actions of the current frame.
+/// - `quest_log`: Resource holding the quests of the user.
+/// - `panel_query`: Query to show or hide the quest log.
+/// - `text_query`: Query to access the quest log text.
+/// - `user_query`: Query to access the user position.
+/// - `name_query`: Query to access the names of the quest givers.
+pub fn update_quest_log(
+    action_input: Res<ActionInput>,
+    quest_log: Res<QuestLog>,
+    mut panel_query: Query<&mut Visibility, With<QuestLogPanel>>,
+    mut text_query: Query<&mut Text, With<QuestLogText>>,
+    user_query: Query<(&Transform, &TilesetOffset), With<IsUser>>,
+    name_query: Query<&Name>,
+) {
+    let (Ok(mut visibility), Ok(mut text)) =
+        (panel_query.get_single_mut(), text_query.get_single_mut())
+    else {
+        return;
+    };
+    if action_input.just_pressed(InputAction::QuestLog) {
+        *visibility = match *visibility {
+            Visibility::Hidden => Visibility::Inherited,
+            _ => Visibility::Hidden,
+        };
+    }
+    if *visibility == Visibility::Hidden {
+        return;
+    }
+
+    let user_pos = user_query
+        .get_single()
+        .map_or(IVec2::ZERO, |(transform, offset)| {
+            player_tile_pos(transform, offset)
+        });
+    let mut content = String::from("Quests\n");
+    if quest_log.is_empty() {
+        content.push_str("No quest yet, ask your Pal!\n");
+    }
+    for quest in quest_log.iter() {
+        let giver = name_query
+            .get(quest.giver)
+            .map_or("someone", |name| name.as_str());
+        content.push_str(&format!("\n{} (from {})\n", quest.title, giver));
+        for objective in quest.objectives.iter() {
+            let mark = if objective.is_done { "x" } else { " " };
+            content.push_str(&format!(
+                "[{}] {}\n",
+                mark,
+                objective.objective.describe(user_pos)
+            ));
+        }
+    }
+    if text.sections[0].value != content {
+        text.sections[0].value = content;
+    }
+}
@@ -1,14 +1,28 @@
 use std::env;
 
+use crate::bundles::bot::BotBundle;
 use crate::bundles::gpt::GptBundle;
+use crate::bundles::interactable::InteractableBundle;
+use crate::bundles::item::ItemDropBundle;
 use crate::bundles::player::PlayerBundle;
 use crate::components::character::*;
-use crate::components::display::IsGameCamera;
+use crate::components::command::ActionQueue;
+use crate::components::display::{
+    DaylightOverlay, HealthBar, IsGameCamera, QuestLogPanel, QuestLogText, TileCursor,
+};
 use crate::components::map::MainTilemapTexture;
+use crate::components::minimap::{Minimap, WorldMap};
+use crate::components::weather::WeatherLayer;
+use crate::constants::bot::*;
 use crate::constants::character::*;
 use crate::constants::display::*;
+use crate::constants::interaction::{CHESTS, SIGNS};
+use crate::constants::item::{ITEM_DROPS, ITEM_SPRITE};
+use crate::constants::map::TILE;
+use crate::constants::minimap::*;
 use crate::constants::sprites::PLAYER_SPRITE;
 use crate::constants::tileset::TEXTURE_PATH;
+use crate::util::minimap::new_map_image;
 use bevy::log;
 use bevy::prelude::*;
 use bevy_pixel_camera::*;
@@ -16,18 +30,20 @@ use bevy_pixel_camera::*;
 /// Sets up the initial game environment.
 ///
 /// This system initializes the game world by loading textures,
-/// spawning the main camera, and creating player and bot entities.
+/// spawning the main camera, tile cursor, weather and daylight overlays, HUD and maps, and creating player, bot and object entities.
 ///
 /// # Parameters
 /// - `commands`: Commands for spawning entities and resources.
 /// - `asset_server`: Resource to load assets.
 /// - `main_texture`: Mutable resource for the main tilemap texture.
 /// - `textures`: Mutable resource for managing texture atlases.
+/// - `images`: Mutable resource for creating the map images.
 pub fn setup(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     mut main_texture: ResMut<MainTilemapTexture>,
     mut texture_atlas: ResMut<Assets<TextureAtlas>>,
+    mut images: ResMut<Assets<Image>>,
 ) {
     // Load tileset
     main_texture.set_handle(asset_server.load(TEXTURE_PATH));
@@ -55,30 +71,225 @@ pub fn setup(
         ))
         .insert(IsUser);
 
-    // Spawn Mittens (GPT)
+    // Spawn the tile cursor, hidden until the cursor is over the window
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: TILE_CURSOR_COLOR,
+                custom_size: Some(Vec2::splat(TILE)),
+                ..Default::default()
+            },
+            transform: Transform::from_xyz(0., 0., TILE_CURSOR_LAYER),
+            visibility: Visibility::Hidden,
+            ..Default::default()
+        },
+        TileCursor,
+    ));
+
+    // Spawn the weather layer, under the daylight overlay
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                overflow: Overflow::clip(),
+                ..Default::default()
+            },
+            z_index: ZIndex::Global(-2),
+            ..Default::default()
+        },
+        WeatherLayer,
+    ));
+
+    // Spawn the daylight overlay, under the rest of the HUD
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                ..Default::default()
+            },
+            z_index: ZIndex::Global(-1),
+            ..Default::default()
+        },
+        DaylightOverlay,
+    ));
+
+    // Spawn the HUD health bar
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                left: Val::Px(HEALTH_BAR_MARGIN),
+                top: Val::Px(HEALTH_BAR_MARGIN),
+                width: Val::Px(HEALTH_BAR_WIDTH),
+                height: Val::Px(HEALTH_BAR_HEIGHT),
+                padding: UiRect::all(Val::Px(HEALTH_BAR_BORDER)),
+                ..Default::default()
+            },
+            background_color: HEALTH_BAR_BACKGROUND.into(),
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                NodeBundle {
+                    style: Style {
+                        width: Val::Percent(100.),
+                        height: Val::Percent(100.),
+                        ..Default::default()
+                    },
+                    background_color: HEALTH_BAR_COLOR.into(),
+                    ..Default::default()
+                },
+                HealthBar,
+            ));
+        });
+
+    // Spawn the quest log, hidden until toggled
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    right: Val::Px(QUEST_LOG_MARGIN),
+                    top: Val::Px(QUEST_LOG_MARGIN),
+                    width: Val::Px(QUEST_LOG_WIDTH),
+                    padding: UiRect::all(Val::Px(QUEST_LOG_PADDING)),
+                    ..Default::default()
+                },
+                background_color: QUEST_LOG_BACKGROUND.into(),
+                visibility: Visibility::Hidden,
+                ..Default::default()
+            },
+            QuestLogPanel,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle {
+                    text: Text::from_section(
+                        "",
+                        TextStyle {
+                            font_size: QUEST_LOG_FONT_SIZE,
+                            color: Color::WHITE,
+                            ..Default::default()
+                        },
+                    ),
+                    ..Default::default()
+                },
+                QuestLogText,
+            ));
+        });
+
+    // Spawn the minimap in the bottom right corner
+    commands.spawn((
+        ImageBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                right: Val::Px(MINIMAP_MARGIN),
+                bottom: Val::Px(MINIMAP_MARGIN),
+                width: Val::Px(MINIMAP_SIZE as f32 * MINIMAP_SCALE),
+                height: Val::Px(MINIMAP_SIZE as f32 * MINIMAP_SCALE),
+                ..Default::default()
+            },
+            image: UiImage::new(images.add(new_map_image(MINIMAP_SIZE, MINIMAP_SIZE))),
+            ..Default::default()
+        },
+        Minimap,
+    ));
+
+    // Spawn the world map over everything, hidden until toggled
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            z_index: ZIndex::Global(1),
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                ImageBundle {
+                    style: Style {
+                        height: Val::Percent(WORLD_MAP_HEIGHT_PERCENT),
+                        aspect_ratio: Some(WORLD_MAP_WIDTH as f32 / WORLD_MAP_HEIGHT as f32),
+                        ..Default::default()
+                    },
+                    image: UiImage::new(
+                        images.add(new_map_image(WORLD_MAP_WIDTH, WORLD_MAP_HEIGHT)),
+                    ),
+                    visibility: Visibility::Hidden,
+                    ..Default::default()
+                },
+                WorldMap,
+            ));
+        });
+
+    // Spawn the interactable objects
+    for (tile_pos, text) in SIGNS {
+        commands.spawn(InteractableBundle::sign(tile_pos, text));
+    }
+    for tile_pos in CHESTS {
+        commands.spawn(InteractableBundle::chest(tile_pos));
+    }
+
+    // Spawn the items lying around
+    let item_texture = &asset_server.load(ITEM_SPRITE);
+    for (tile_pos, stack) in ITEM_DROPS {
+        commands.spawn(ItemDropBundle::new(
+            stack,
+            tile_pos,
+            item_texture,
+            &mut texture_atlas,
+        ));
+    }
+
+    // Spawn bots (GPT)
     let option_key = env::var("PAL_KEY");
     let model = env::var("PAL_MODEL").unwrap_or_else(|_| {
-        log::info!("No model provided, using gpt-3.5-turbo-1106");
-        "gpt-3.5-turbo-1106".into()
+        log::info!("No model provided, using {}", DEFAULT_MODEL);
+        DEFAULT_MODEL.into()
     });
     let url = env::var("PAL_URL").unwrap_or_else(|_| {
-        log::info!("No API URK provided, using https://api.openai.com/v1/chat/completions");
-        "https://api.openai.com/v1/chat/completions".into()
+        log::info!("No API URL provided, using {}", DEFAULT_API_URL);
+        DEFAULT_API_URL.into()
     });
 
-    if let Ok(key) = option_key {
-        let option_gpt = GptBundle::new(
-            MITTENS_SPAWN,
-            player_texture,
-            &mut texture_atlas,
-            key,
-            model,
-            url,
-        );
+    if option_key.is_err() {
+        log::info!("No API key provided! Bots will use their offline brain.")
+    }
+
+    for definition in BOT_DEFINITIONS.iter() {
+        let texture = &asset_server.load(definition.sprite);
+        let option_gpt = option_key.as_ref().ok().and_then(|key| {
+            GptBundle::new(
+                definition,
+                texture,
+                &mut texture_atlas,
+                key.clone(),
+                definition.model.map_or_else(|| model.clone(), String::from),
+                url.clone(),
+            )
+        });
+
+        log::info!("Spawning bot {}", definition.name);
         if let Some(gpt) = option_gpt {
             commands.spawn(gpt).insert(IsBot);
+        } else {
+            commands
+                .spawn(BotBundle::new(
+                    definition,
+                    texture,
+                    &mut texture_atlas,
+                    ActionQueue::default(),
+                ))
+                .insert(IsBot);
         }
-    } else {
-        log::info!("No API key provided! PAL will not be spawned.")
     }
 }
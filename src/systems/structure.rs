@@ -0,0 +1,118 @@
+use bevy::prelude::*;
+
+use crate::bundles::interactable::InteractableBundle;
+use crate::bundles::structure::StructurePieceBundle;
+use crate::components::map::{ChunkMap, Terrain};
+use crate::components::structure::*;
+use crate::constants::map::CHUNK_SIZE;
+use crate::constants::structure::{SIGN_NOTHING_TEXT, SIGN_WELCOME_TEXT};
+use crate::util::position::{describe_offset, tile_pos_to_chunk_pos, tile_pos_to_region_pos};
+use crate::util::structure::{closest_structure, structure_in_region, PlacedStructure};
+
+/// Stamps the structures on the generated chunks, and registers them.
+///
+/// Each chunk spawns the pieces of its region structure lying on its own
+/// tiles, so a structure spanning several chunks is completed as they load.
+///
+/// # Parameters
+/// - `commands`: Commands for spawning the structure pieces.
+/// - `chunk_map`: Resource containing all chunk data.
+/// - `terrain`: Relief levels of the loaded tiles.
+/// - `chunk_structures`: Resource remembering the structure pieces of each chunk.
+/// - `points_of_interest`: Resource registering the named structures.
+pub fn spawn_structures(
+    mut commands: Commands,
+    chunk_map: Res<ChunkMap>,
+    terrain: Terrain,
+    mut chunk_structures: ResMut<ChunkStructures>,
+    mut points_of_interest: ResMut<PointsOfInterest>,
+) {
+    for chunk_pos in chunk_map.keys() {
+        if chunk_structures.contains_key(chunk_pos) {
+            continue;
+        }
+
+        // The pieces appear together with the tiles of the chunk
+        let base = *chunk_pos * CHUNK_SIZE.as_ivec2();
+        if terrain.level(&base).is_none() {
+            continue;
+        }
+
+        let region = tile_pos_to_region_pos(&base);
+        let mut pieces = Vec::new();
+        if let Some(structure) = structure_in_region(region) {
+            points_of_interest
+                .entry(region)
+                .or_insert_with(|| PointOfInterest {
+                    name: structure.name.to_string(),
+                    kind: structure.template.kind,
+                    center: structure.center(),
+                });
+
+            for (tile_pos, piece) in structure.pieces() {
+                if tile_pos_to_chunk_pos(&tile_pos) != *chunk_pos {
+                    continue;
+                }
+                let entity = match piece {
+                    StructurePiece::Sign => commands
+                        .spawn((
+                            InteractableBundle::sign(
+                                tile_pos,
+                                &sign_text(&structure, region, tile_pos),
+                            ),
+                            piece,
+                        ))
+                        .id(),
+                    _ => commands
+                        .spawn(StructurePieceBundle::new(piece, tile_pos))
+                        .id(),
+                };
+                pieces.push(entity);
+            }
+        }
+        chunk_structures.insert(*chunk_pos, pieces);
+    }
+}
+
+/// Despawns the structure pieces of despawned chunks.
+///
+/// # Parameters
+/// - `commands`: Commands for despawning the structure pieces.
+/// - `chunk_map`: Resource containing all chunk data.
+/// - `chunk_structures`: Resource remembering the structure pieces of each chunk.
+pub fn despawn_structures(
+    mut commands: Commands,
+    chunk_map: Res<ChunkMap>,
+    mut chunk_structures: ResMut<ChunkStructures>,
+) {
+    chunk_structures.retain(|chunk_pos, pieces| {
+        if chunk_map.contains_key(chunk_pos) {
+            return true;
+        }
+        for piece in pieces.iter() {
+            if let Some(entity) = commands.get_entity(*piece) {
+                entity.despawn_recursive();
+            }
+        }
+        false
+    });
+}
+
+/// Writes the text of a structure sign.
+///
+/// Signposts show the way to the closest structure, other signs welcome to theirs.
+fn sign_text(structure: &PlacedStructure, region: IVec2, tile_pos: IVec2) -> String {
+    if structure.template.kind != StructureKind::Signpost {
+        return format!("{} {}!", SIGN_WELCOME_TEXT, structure.name);
+    }
+    closest_structure(region, tile_pos).map_or_else(
+        || SIGN_NOTHING_TEXT.to_string(),
+        |closest| {
+            format!(
+                "{}: {}.",
+                closest.name,
+                describe_offset(closest.center() - tile_pos)
+            )
+        },
+    )
+}
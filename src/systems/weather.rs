@@ -0,0 +1,157 @@
+use bevy::audio::Volume;
+use bevy::log;
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::components::character::IsUser;
+use crate::components::clock::WorldClock;
+use crate::components::map::Terrain;
+use crate::components::texture::TilesetOffset;
+use crate::components::weather::*;
+use crate::components::wildlife::Biome;
+use crate::constants::weather::WEATHER_PARTICLE_SPAWN_RATE;
+use crate::util::position::player_tile_pos;
+
+/// Changes the weather at the start of every weather period.
+///
+/// Periods are replayed one by one, and wait for the terrain under the user
+/// to be generated, so the weather only depends on the seed, the clock and
+/// the biomes the user went through.
+///
+/// # Parameters
+/// - `clock`: Resource keeping the in-game time.
+/// - `weather`: Resource holding the current weather.
+/// - `terrain`: Relief levels of the loaded tiles.
+/// - `weather_events`: For announcing the weather changes.
+/// - `user_query`: Query to access the user position.
+pub fn change_weather(
+    clock: Res<WorldClock>,
+    mut weather: ResMut<Weather>,
+    terrain: Terrain,
+    mut weather_events: EventWriter<WeatherChangeEvent>,
+    user_query: Query<(&Transform, &TilesetOffset), With<IsUser>>,
+) {
+    let period = Weather::period_at(&clock);
+    if weather.period >= period {
+        return;
+    }
+    let Some(biome) = user_query
+        .iter()
+        .next()
+        .and_then(|(transform, offset)| terrain.level(&player_tile_pos(transform, offset)))
+        // The weather over water is the one of the shore
+        .map(|level| Biome::from_level(level).unwrap_or(Biome::Shore))
+    else {
+        return;
+    };
+
+    let previous = weather.kind;
+    while weather.period < period {
+        weather.period += 1;
+        weather.kind = weather.kind.next(biome, weather.period);
+    }
+    if weather.kind != previous {
+        log::info!("The weather changes from {} to {}", previous, weather.kind);
+        weather_events.send(WeatherChangeEvent {
+            previous,
+            current: weather.kind,
+        });
+    }
+}
+
+/// Updates the weather overlay, and spawns and moves its particles.
+///
+/// Particles of a previous weather fall off the screen before being despawned.
+///
+/// # Parameters
+/// - `commands`: Commands for spawning and despawning particles.
+/// - `time`: Resource containing time information.
+/// - `weather`: Resource holding the current weather.
+/// - `layer_query`: Query to access the node holding the weather effects.
+/// - `particle_query`: Query to access and move the particles.
+pub fn update_weather_effects(
+    mut commands: Commands,
+    time: Res<Time>,
+    weather: Res<Weather>,
+    mut layer_query: Query<(Entity, &mut BackgroundColor), With<WeatherLayer>>,
+    mut particle_query: Query<(Entity, &mut Style, &mut WeatherParticle)>,
+) {
+    let Ok((layer, mut overlay)) = layer_query.get_single_mut() else {
+        return;
+    };
+    let definition = weather.kind.definition();
+    *overlay = definition.overlay.into();
+
+    let mut count = 0;
+    for (entity, mut style, mut particle) in particle_query.iter_mut() {
+        let velocity = particle.velocity;
+        particle.position += velocity * time.delta_seconds();
+        if particle.position.y > 100. {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        style.left = Val::Percent(particle.position.x);
+        style.top = Val::Percent(particle.position.y);
+        count += 1;
+    }
+
+    let Some(particles) = &definition.particles else {
+        return;
+    };
+    let mut rng = rand::thread_rng();
+    let missing = particles.count.saturating_sub(count);
+    for _ in 0..missing.min(WEATHER_PARTICLE_SPAWN_RATE) {
+        // Particles drifting sideways start further on the other side
+        let position = Vec2::new(rng.gen_range(-20. ..120.), rng.gen_range(-10. ..0.));
+        let velocity =
+            particles.velocity + Vec2::X * rng.gen_range(-particles.drift..=particles.drift);
+        let particle = commands
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        left: Val::Percent(position.x),
+                        top: Val::Percent(position.y),
+                        width: Val::Px(particles.size.x),
+                        height: Val::Px(particles.size.y),
+                        ..Default::default()
+                    },
+                    background_color: particles.color.into(),
+                    ..Default::default()
+                },
+                WeatherParticle { position, velocity },
+            ))
+            .id();
+        commands.entity(layer).add_child(particle);
+    }
+}
+
+/// Starts the looping sound of the new weather, stopping the previous one.
+///
+/// # Parameters
+/// - `commands`: Commands for spawning and despawning the sound entities.
+/// - `asset_server`: For loading the sounds.
+/// - `weather_events`: The weather changes.
+/// - `sound_query`: Query to access the sound of the previous weather.
+pub fn play_weather_sound(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut weather_events: EventReader<WeatherChangeEvent>,
+    sound_query: Query<Entity, With<WeatherSound>>,
+) {
+    let Some(event) = weather_events.read().last() else {
+        return;
+    };
+    for entity in sound_query.iter() {
+        commands.entity(entity).despawn();
+    }
+    if let Some(sound) = &event.current.definition().sound {
+        commands.spawn((
+            AudioBundle {
+                source: asset_server.load(sound.path),
+                settings: PlaybackSettings::LOOP.with_volume(Volume::new_relative(sound.volume)),
+            },
+            WeatherSound,
+        ));
+    }
+}
@@ -0,0 +1,299 @@
+use std::sync::atomic::Ordering;
+
+use bevy::prelude::*;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use strum::IntoEnumIterator;
+
+use crate::bundles::mob::CreatureMobBundle;
+use crate::components::action::*;
+use crate::components::character::*;
+use crate::components::combat::Hitbox;
+use crate::components::map::{ChunkMap, Occupancy, Terrain};
+use crate::components::texture::TilesetOffset;
+use crate::components::wildlife::*;
+use crate::constants::generation::WATER_LEVEL;
+use crate::constants::map::{CHUNK_SIZE, TILE};
+use crate::constants::wildlife::*;
+use crate::util::noise::hash_position;
+use crate::util::position::{pixel_pos_to_tile_pos, player_tile_pos};
+
+// Define a type for creature queries
+type CreatureQuery<'a> = (
+    Entity,
+    &'a mut Creature,
+    &'a Busy,
+    &'a mut Action,
+    &'a mut ActionTimer,
+    &'a ActionDurationPHF,
+    &'a Transform,
+    &'a TilesetOffset,
+    &'a SpawnPoint,
+);
+
+// Define a filter matching the living users and bots
+type IsLivingCharacter = (Or<(With<IsUser>, With<IsBot>)>, Without<Dead>);
+
+/// Populates the generated chunks with creatures.
+///
+/// Each chunk is populated once, from a random generator seeded with its
+/// position: the same chunk always spawns the same creatures, on tiles
+/// matching their biomes.
+///
+/// # Parameters
+/// - `commands`: Commands for spawning the creatures.
+/// - `chunk_map`: Resource containing all chunk data.
+/// - `terrain`: Relief levels of the loaded tiles.
+/// - `occupancy`: Tiles occupied by characters and objects, never spawned on.
+/// - `chunk_creatures`: Resource remembering the creatures of each chunk.
+/// - `asset_server`: For getting the creature textures.
+/// - `texture_atlas`: Registering the creature textures.
+pub fn spawn_wildlife(
+    mut commands: Commands,
+    chunk_map: Res<ChunkMap>,
+    terrain: Terrain,
+    occupancy: Res<Occupancy>,
+    mut chunk_creatures: ResMut<ChunkCreatures>,
+    asset_server: Res<AssetServer>,
+    mut texture_atlas: ResMut<Assets<TextureAtlas>>,
+) {
+    for chunk_pos in chunk_map.keys() {
+        if chunk_creatures.contains_key(chunk_pos) {
+            continue;
+        }
+
+        // The tiles of a chunk are generated asynchronously
+        let base = *chunk_pos * CHUNK_SIZE.as_ivec2();
+        if terrain.level(&base).is_none() {
+            continue;
+        }
+
+        let mut rng = StdRng::seed_from_u64(hash_position(WILDLIFE_SEED, *chunk_pos));
+        let mut creatures = Vec::new();
+        for _ in 0..WILDLIFE_SPAWN_ATTEMPTS {
+            // Every attempt draws the same numbers, whatever its outcome
+            let spawns = rng.gen_bool(WILDLIFE_SPAWN_CHANCE);
+            let tile_pos = base
+                + IVec2::new(
+                    rng.gen_range(0..CHUNK_SIZE.x as i32),
+                    rng.gen_range(0..CHUNK_SIZE.y as i32),
+                );
+            let species_seed: u64 = rng.gen();
+            let seed: u64 = rng.gen();
+            if !spawns || occupancy.contains_key(&tile_pos) {
+                continue;
+            }
+
+            let Some(species) = terrain
+                .level(&tile_pos)
+                .and_then(Biome::from_level)
+                .and_then(|biome| pick_species(biome, &mut StdRng::seed_from_u64(species_seed)))
+            else {
+                continue;
+            };
+            let creature = commands
+                .spawn(CreatureMobBundle::new(
+                    tile_pos.as_vec2() * TILE,
+                    Creature::new(species, seed),
+                    &asset_server.load(species.sprite),
+                    &mut texture_atlas,
+                ))
+                .id();
+            creatures.push(creature);
+        }
+        chunk_creatures.insert(*chunk_pos, creatures);
+    }
+}
+
+/// Despawns the creatures of despawned chunks, and the killed creatures.
+///
+/// Killed creatures come back when their chunk is populated again.
+///
+/// # Parameters
+/// - `commands`: Commands for despawning the creatures.
+/// - `chunk_map`: Resource containing all chunk data.
+/// - `chunk_creatures`: Resource remembering the creatures of each chunk.
+/// - `dead_query`: Query to access the creatures that just died.
+pub fn despawn_wildlife(
+    mut commands: Commands,
+    chunk_map: Res<ChunkMap>,
+    mut chunk_creatures: ResMut<ChunkCreatures>,
+    dead_query: Query<Entity, (With<Creature>, Added<Dead>)>,
+) {
+    for entity in dead_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    chunk_creatures.retain(|chunk_pos, creatures| {
+        if chunk_map.contains_key(chunk_pos) {
+            return true;
+        }
+        for creature in creatures.iter() {
+            if dead_query.contains(*creature) {
+                continue;
+            }
+            if let Some(entity) = commands.get_entity(*creature) {
+                entity.despawn_recursive();
+            }
+        }
+        false
+    });
+}
+
+/// Drives the creatures, at a fixed interval once their action is finished.
+///
+/// Passive creatures run away from the characters in sight. Hostile ones
+/// chase them, and attack once adjacent, until they are too far from their
+/// spawn point. Otherwise, creatures wander around their spawn point.
+///
+/// # Parameters
+/// - `commands`: Commands for spawning the hitboxes of attacks.
+/// - `time`: Resource containing time information.
+/// - `terrain`: Relief levels of the loaded tiles.
+/// - `occupancy`: Tiles occupied by characters, avoided and reserved by creatures.
+/// - `creature_query`: Query to access the living creatures and their actions.
+/// - `character_query`: Query to access the positions of living users and bots.
+pub fn run_creatures(
+    mut commands: Commands,
+    time: Res<Time>,
+    terrain: Terrain,
+    mut occupancy: ResMut<Occupancy>,
+    mut creature_query: Query<CreatureQuery, Without<Dead>>,
+    character_query: Query<(Entity, &Transform, &TilesetOffset), IsLivingCharacter>,
+) {
+    for (
+        entity,
+        mut creature,
+        busy,
+        mut action,
+        mut timer,
+        duration,
+        transform,
+        offset,
+        spawn_point,
+    ) in creature_query.iter_mut()
+    {
+        let creature = &mut *creature;
+        creature.attack_timer.tick(time.delta());
+        if !creature.think_timer.tick(time.delta()).just_finished() || busy.load(Ordering::Acquire)
+        {
+            continue;
+        }
+
+        let species = creature.species;
+        let position = player_tile_pos(transform, offset);
+        let home = pixel_pos_to_tile_pos(spawn_point);
+        let closest = character_query
+            .iter()
+            .map(|(other, transform, offset)| (other, player_tile_pos(transform, offset)))
+            .filter(|(_, other_pos)| (*other_pos - position).abs().max_element() <= species.sight)
+            .min_by_key(|(_, other_pos)| (*other_pos - position).abs().max_element());
+
+        let is_home_close = (position - home).abs().max_element() < WILDLIFE_CHASE_DISTANCE;
+        creature.state = match (species.temperament, closest) {
+            (Temperament::Passive, Some((other, _))) => CreatureState::Flee(other),
+            (Temperament::Hostile, Some((other, other_pos)))
+                if (other_pos - position).abs().max_element() <= 1 =>
+            {
+                CreatureState::Attack(other)
+            }
+            (Temperament::Hostile, Some((other, _))) if is_home_close => {
+                CreatureState::Chase(other)
+            }
+            _ => CreatureState::Wander,
+        };
+
+        let directions = safe_directions(&terrain, &occupancy, entity, &position);
+        let distance_after = |direction: &ActionDirection, target: IVec2| {
+            (position + direction.to_vector() - target).length_squared()
+        };
+        let new_action = match (creature.state, closest) {
+            (CreatureState::Flee(_), Some((_, other_pos))) => directions
+                .iter()
+                .max_by_key(|direction| distance_after(direction, other_pos))
+                .map(|direction| Action::new(ActionKind::Run, direction.clone())),
+            (CreatureState::Chase(_), Some((_, other_pos))) => directions
+                .iter()
+                .min_by_key(|direction| distance_after(direction, other_pos))
+                .map(|direction| Action::new(ActionKind::Run, direction.clone())),
+            (CreatureState::Attack(_), Some((_, other_pos))) => {
+                // Between two attacks, the creature faces its target
+                let kind = if creature.attack_timer.finished() {
+                    creature.attack_timer.reset();
+                    ActionKind::Attack
+                } else {
+                    ActionKind::Stand
+                };
+                ActionDirection::from_vector(other_pos - position)
+                    .map(|direction| Action::new(kind, direction))
+            }
+            _ if (position - home).abs().max_element() > WILDLIFE_ROAM_DISTANCE => directions
+                .iter()
+                .min_by_key(|direction| distance_after(direction, home))
+                .map(|direction| Action::new(ActionKind::Walk, direction.clone())),
+            _ if creature.rng.gen_bool(WILDLIFE_WANDER_CHANCE) => directions
+                .choose(&mut creature.rng)
+                .map(|direction| Action::new(ActionKind::Walk, direction.clone())),
+            _ => None,
+        };
+
+        let Some((new_action, terrain_cost)) =
+            new_action.and_then(|new_action| terrain.plan_action(new_action, &position))
+        else {
+            continue;
+        };
+        if occupancy.reserve(entity, &new_action, &position).is_err() {
+            continue;
+        }
+        if new_action.kind == ActionKind::Attack {
+            commands.spawn(Hitbox::new(
+                entity,
+                &new_action,
+                &position,
+                species.damage,
+                duration.hit_delay(),
+            ));
+        }
+        *action = new_action;
+        *timer = duration.generate_timer(&action, terrain_cost);
+        busy.store(true, Ordering::Release);
+    }
+}
+
+/// Picks the creature spawning in a biome.
+///
+/// # Parameters
+/// - `biome`: The biome of the spawning tile.
+/// - `rng`: The random number generator of the spawn attempt.
+///
+/// # Returns
+/// The creature definition, or `None` if nothing lives in the biome.
+fn pick_species(biome: Biome, rng: &mut StdRng) -> Option<&'static CreatureDefinition> {
+    let candidates: Vec<_> = CREATURES
+        .iter()
+        .filter(|species| species.biomes.contains(&biome))
+        .collect();
+    let dist = WeightedIndex::new(candidates.iter().map(|species| species.weight)).ok()?;
+    Some(candidates[dist.sample(rng)])
+}
+
+/// Lists the directions a creature can walk to without falling, swimming or bumping into anyone.
+fn safe_directions(
+    terrain: &Terrain,
+    occupancy: &Occupancy,
+    entity: Entity,
+    position: &IVec2,
+) -> Vec<ActionDirection> {
+    ActionDirection::iter()
+        .filter(|direction| {
+            let action = Action::new(ActionKind::Walk, direction.clone());
+            let target = *position + action.get_raw_transformation();
+            terrain.is_action_possible(&action, position)
+                && terrain.level(&target) != Some(WATER_LEVEL)
+                && terrain.fall_height(position, &target) == 0
+                && occupancy.blocker(&target, entity).is_none()
+        })
+        .collect()
+}
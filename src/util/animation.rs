@@ -14,3 +14,17 @@ use benimator::{Animation, FrameRate};
 pub fn new_animation(indices: impl IntoIterator<Item = usize>, fps: f64) -> Animation {
     Animation::from_indices(indices, FrameRate::from_fps(fps))
 }
+
+/// Eases the progress of a movement.
+///
+/// # Parameters
+/// - `progress`: The elapsed part of the movement, from 0 to 1.
+/// - `strength`: The blend between a linear movement (0) and a smoothstep (1).
+///
+/// # Returns
+/// The covered part of the movement, from 0 to 1.
+pub fn ease_movement(progress: f32, strength: f32) -> f32 {
+    let progress = progress.clamp(0., 1.);
+    let smoothstep = progress * progress * (3. - 2. * progress);
+    progress + (smoothstep - progress) * strength
+}
@@ -1,23 +1,52 @@
 use bevy::prelude::*;
+use bevy::text::Text2dBounds;
 
 use crate::{
     bundles::effect::EffectBundle,
     components::animation::{AnimationSpriteGrid, DefinedAnimation},
+    components::display::{Lifetime, SpeechBubble},
+    constants::display::*,
 };
 
+/// Spawns an effect, despawned after `duration` seconds.
 pub fn spawn_effect(
     commands: &mut Commands,
     sprite_grid: &AnimationSpriteGrid,
     animation: DefinedAnimation,
     position: Vec3,
+    duration: f32,
     texture: &Handle<Image>,
     texture_atlas: &mut ResMut<Assets<TextureAtlas>>,
 ) {
-    commands.spawn(EffectBundle::new(
-        animation,
-        sprite_grid,
-        position,
-        texture,
-        texture_atlas,
+    commands.spawn((
+        EffectBundle::new(animation, sprite_grid, position, texture, texture_atlas),
+        Lifetime(Timer::from_seconds(duration, TimerMode::Once)),
     ));
 }
+
+/// Spawns a temporary speech bubble above a character.
+pub fn spawn_speech(commands: &mut Commands, speaker: Entity, text: String) {
+    let speech = commands
+        .spawn((
+            Text2dBundle {
+                text: Text::from_section(
+                    text,
+                    TextStyle {
+                        font_size: SPEECH_FONT_SIZE,
+                        color: Color::WHITE,
+                        ..Default::default()
+                    },
+                )
+                .with_alignment(TextAlignment::Center),
+                text_2d_bounds: Text2dBounds {
+                    size: Vec2::new(SPEECH_WIDTH, f32::INFINITY),
+                },
+                transform: Transform::from_translation(SPEECH_RELATIVE_POSITION),
+                ..Default::default()
+            },
+            Lifetime(Timer::from_seconds(SPEECH_DURATION, TimerMode::Once)),
+            SpeechBubble,
+        ))
+        .id();
+    commands.entity(speaker).add_child(speech);
+}
@@ -121,6 +121,15 @@ impl Default for ModelConfiguration {
     }
 }
 
+impl ChatMessage {
+    pub fn new<S: Into<String>>(role: Role, content: S) -> Self {
+        Self {
+            role,
+            content: content.into(),
+        }
+    }
+}
+
 impl CompletionResponse {
     pub fn message(&self) -> &ChatMessage {
         &self.message_choices.first().unwrap().message
@@ -140,20 +149,16 @@ impl ChatGPT {
         Ok(Self { client, config })
     }
 
-    pub async fn send_message<S: Into<String>>(
+    pub async fn send_messages(
         &self,
-        message: S,
-        role: Role,
+        messages: &Vec<ChatMessage>,
     ) -> Result<CompletionResponse, Error> {
         let response = self
             .client
             .post(self.config.api_url.clone())
             .json(&CompletionRequest {
                 model: self.config.engine.as_ref(),
-                messages: &vec![ChatMessage {
-                    role,
-                    content: message.into(),
-                }],
+                messages,
                 stream: false,
                 temperature: self.config.temperature,
                 top_p: self.config.top_p,
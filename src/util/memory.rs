@@ -0,0 +1,126 @@
+use std::collections::VecDeque;
+
+use crate::constants::bot::*;
+
+const SUMMARY_PREFIX: &str = "SUMMARY:";
+const FACT_PREFIX: &str = "FACT:";
+
+/// Long-term memory of a conversation.
+///
+/// Recent turns are kept verbatim, older ones are folded into a summary,
+/// and salient facts are kept aside to be injected into the system prompt.
+#[derive(Clone, Default)]
+pub struct ConversationMemory {
+    turns: VecDeque<String>,
+    summary: Option<String>,
+    facts: VecDeque<String>,
+}
+
+/// Estimates the number of tokens of a text.
+///
+/// # Parameters
+/// - `text`: The text sent to the model.
+///
+/// # Returns
+/// A rough token count, good enough to decide when to summarize.
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(MEMORY_CHARACTERS_PER_TOKEN)
+}
+
+impl ConversationMemory {
+    /// Adds a turn to the history.
+    pub fn push_turn(&mut self, turn: String) {
+        self.turns.push_back(turn);
+    }
+
+    /// Estimates the number of tokens of the whole memory.
+    pub fn token_count(&self) -> usize {
+        estimate_tokens(&self.history()) + estimate_tokens(&self.system_section())
+    }
+
+    /// Checks if the oldest turns should be summarized.
+    pub fn needs_summary(&self) -> bool {
+        self.turns.len() > MEMORY_KEPT_TURNS && self.token_count() > MEMORY_SUMMARY_THRESHOLD
+    }
+
+    /// Builds the text to summarize.
+    ///
+    /// # Returns
+    /// The text sent to the model and the number of turns it contains,
+    /// to be given back to `apply_summary`.
+    pub fn summary_request(&self) -> (String, usize) {
+        let count = self.turns.len().saturating_sub(MEMORY_KEPT_TURNS);
+        let mut request = String::new();
+        if let Some(summary) = &self.summary {
+            request.push_str(&format!("Previous summary: {}\n", summary));
+        }
+        for turn in self.turns.iter().take(count) {
+            request.push_str(turn);
+            request.push('\n');
+        }
+        (request, count)
+    }
+
+    /// Replaces the summarized turns with the model's answer.
+    ///
+    /// # Parameters
+    /// - `response`: Answer to the summary request.
+    /// - `count`: Number of turns that were summarized.
+    pub fn apply_summary(&mut self, response: &str, count: usize) {
+        let mut summary = Vec::new();
+        for line in response.lines().map(str::trim) {
+            if let Some(fact) = line.strip_prefix(FACT_PREFIX) {
+                self.remember(fact.trim());
+            } else if let Some(text) = line.strip_prefix(SUMMARY_PREFIX) {
+                summary.push(text.trim());
+            } else if !line.is_empty() {
+                summary.push(line);
+            }
+        }
+
+        if !summary.is_empty() {
+            self.summary = Some(summary.join(" "));
+        }
+        self.forget(count);
+    }
+
+    /// Drops the oldest turns.
+    pub fn forget(&mut self, count: usize) {
+        self.turns.drain(..count.min(self.turns.len()));
+    }
+
+    /// Stores a salient fact, the oldest one is dropped when the store is full.
+    pub fn remember(&mut self, fact: &str) {
+        if fact.is_empty() || self.facts.iter().any(|f| f.eq_ignore_ascii_case(fact)) {
+            return;
+        }
+        if self.facts.len() >= MEMORY_MAX_FACTS {
+            self.facts.pop_front();
+        }
+        self.facts.push_back(fact.to_string());
+    }
+
+    /// Formats the facts and summary for the system prompt.
+    pub fn system_section(&self) -> String {
+        let mut section = String::new();
+        if !self.facts.is_empty() {
+            section.push_str("Things you remember:\n");
+            for fact in &self.facts {
+                section.push_str(&format!("- {}\n", fact));
+            }
+        }
+        if let Some(summary) = &self.summary {
+            section.push_str(&format!("Earlier: {}\n", summary));
+        }
+        section
+    }
+
+    /// Formats the recent turns.
+    pub fn history(&self) -> String {
+        self.turns
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
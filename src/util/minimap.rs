@@ -0,0 +1,74 @@
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+
+use crate::components::exploration::ExploredMap;
+use crate::components::minimap::MapMarker;
+use crate::constants::minimap::*;
+
+/// Creates a map image, one pixel per tile.
+pub fn new_map_image(width: u32, height: u32) -> Image {
+    Image::new_fill(
+        Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &MAP_UNEXPLORED_COLOR,
+        TextureFormat::Rgba8UnormSrgb,
+    )
+}
+
+/// Draws the explored tiles around a center tile and the markers on a map image.
+///
+/// Each pixel of the image is a tile, north being up.
+///
+/// # Parameters
+/// - `image`: The RGBA map image.
+/// - `explored`: The explored tiles, the others are left dark.
+/// - `center`: The tile drawn at the center of the image.
+/// - `markers`: The markers to draw over the tiles, with their tile positions.
+pub fn draw_map(
+    image: &mut Image,
+    explored: &ExploredMap,
+    center: IVec2,
+    markers: &[(IVec2, MapMarker)],
+) {
+    let size = image.size().as_ivec2();
+    let top_left = center + IVec2::new(-size.x / 2, size.y / 2);
+    let pixel_of = |tile_pos: IVec2| {
+        let pixel = IVec2::new(tile_pos.x - top_left.x, top_left.y - tile_pos.y);
+        (pixel.cmpge(IVec2::ZERO).all() && pixel.cmplt(size).all())
+            .then(|| (pixel.y * size.x + pixel.x) as usize * 4)
+    };
+
+    for y in 0..size.y {
+        for x in 0..size.x {
+            let tile_pos = top_left + IVec2::new(x, -y);
+            let color = explored
+                .level(&tile_pos)
+                .and_then(|level| MAP_LEVEL_COLORS.get(level as usize))
+                .unwrap_or(&MAP_UNEXPLORED_COLOR);
+            let index = (y * size.x + x) as usize * 4;
+            image.data[index..index + 4].copy_from_slice(color);
+        }
+    }
+
+    // Places first, the characters stay visible over them
+    let mut markers = markers.to_vec();
+    markers.sort_by_key(|(_, marker)| *marker != MapMarker::Place);
+    for (tile_pos, marker) in markers {
+        let color = match marker {
+            MapMarker::User => &MAP_USER_COLOR,
+            MapMarker::Bot => &MAP_BOT_COLOR,
+            MapMarker::Place => &MAP_PLACE_COLOR,
+        };
+        for dy in -MAP_MARKER_RADIUS..=MAP_MARKER_RADIUS {
+            for dx in -MAP_MARKER_RADIUS..=MAP_MARKER_RADIUS {
+                if let Some(index) = pixel_of(tile_pos + IVec2::new(dx, dy)) {
+                    image.data[index..index + 4].copy_from_slice(color);
+                }
+            }
+        }
+    }
+}
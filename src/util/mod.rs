@@ -4,6 +4,10 @@
 pub mod animation;
 // Interact with GPT API
 pub mod gpt;
+// Long-term memory of GPT conversations
+pub mod memory;
+// Parses the command language of bots
+pub mod parser;
 // Handles noise generation for terrain or other procedural generation needs
 pub mod noise;
 // Deals with position-related utilities, like conversions between different coordinate systems
@@ -14,3 +18,7 @@ pub mod tile;
 pub mod distribution;
 // Effect utils (spawn effects..)
 pub mod effect;
+// Places the structures of the generation regions
+pub mod structure;
+// Draws the minimap and the world map
+pub mod minimap;
@@ -1,9 +1,22 @@
 use crate::constants::generation::*;
+use bevy::math::IVec2;
 use noise::{Fbm, NoiseFn, Perlin, Seedable};
+use once_cell::sync::Lazy;
 use quick_cache::sync::Cache;
 
 const _TOTAL_SAMPLE: f64 = SAMPLE_NUMBER as f64 * SAMPLE_NUMBER as f64;
 
+/// The relief noise of the world, shared by the chunk and structure generation.
+pub static NOISE: Lazy<TiledNoise> = Lazy::new(|| {
+    TiledNoise::new(
+        WORLD_SEED as u32,
+        &LAYER_RANGE,
+        NOISE_ZOOM,
+        SAMPLE_NUMBER,
+        CACHE_SIZE,
+    )
+});
+
 /// A struct representing a tiled noise generator.
 ///
 /// This struct uses Fbm (Fractal Brownian Motion) noise algorithm for generating noise values.
@@ -124,4 +137,53 @@ impl<'a> TiledNoise<'a> {
             + if got_sw { 0b000_0_0_100 } else { 0 }
             + if got_se { 0b000_0_0_001 } else { 0 })
     }
+
+    /// Finds where a ramp going down from a tile leads, if the tile hosts one.
+    ///
+    /// # Parameters
+    /// - `value`: The value of the current tile.
+    /// - `mask`: The mask of the current tile, see `get_mask`.
+    /// - `x`: The x-coordinate of the tile.
+    /// - `y`: The y-coordinate of the tile.
+    ///
+    /// # Returns
+    /// The downhill direction as `(x, y)` offsets, or `None`.
+    ///
+    /// Ramps are placed on straight edges dropping exactly one level,
+    /// every `RAMP_SPACING` tiles along the edge.
+    #[allow(clippy::unusual_byte_groupings)]
+    pub fn get_ramp(&self, value: u32, mask: u32, x: i32, y: i32) -> Option<(i32, i32)> {
+        // Downhill offset, cardinal bit, bits allowed on that side, position along the edge
+        let edges = [
+            ((0, 1), 0b010_0_0_000u32, 0b111_0_0_000u32, x),
+            ((0, -1), 0b000_0_0_010, 0b000_0_0_111, x),
+            ((-1, 0), 0b000_1_0_000, 0b100_1_0_100, y),
+            ((1, 0), 0b000_0_1_000, 0b001_0_1_001, y),
+        ];
+
+        edges
+            .into_iter()
+            .find(|(_, cardinal, side, _)| mask & cardinal != 0 && mask & !side == 0)
+            .filter(|(_, _, _, along)| along.rem_euclid(RAMP_SPACING) == 0)
+            .map(|(down, _, _, _)| down)
+            .filter(|(dx, dy)| self.get_value(x + dx, y + dy) + 1 == value)
+    }
+}
+
+/// Hashes a position with a seed, for reproducible random generation.
+///
+/// # Parameters
+/// - `seed`: Seed value shared by every position.
+/// - `position`: The position, usually of a chunk.
+///
+/// # Returns
+/// A well mixed 64 bits value, different for nearby positions.
+pub fn hash_position(seed: u64, position: IVec2) -> u64 {
+    // SplitMix64 finalizer over the packed coordinates
+    let mut hash = seed
+        ^ ((position.x as u32 as u64) << 32 | position.y as u32 as u64)
+            .wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    hash = (hash ^ (hash >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    hash = (hash ^ (hash >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    hash ^ (hash >> 31)
 }
@@ -0,0 +1,701 @@
+use bevy::math::IVec2;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::components::action::{Action, ActionDirection, ActionKind};
+use crate::components::command::{AttackTarget, Command, FollowTarget};
+use crate::components::item::ItemAction;
+use crate::components::quest::{Objective, QuestProposal};
+use crate::constants::bot::*;
+use crate::constants::item::ITEM_REGISTRY;
+use crate::constants::quest::{QUEST_MAX_ITEMS, QUEST_MAX_OBJECTIVES};
+
+/// Error raised when a command text cannot be parsed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    /// Line of the error, starting at 1.
+    pub line: usize,
+    /// Column of the error, starting at 1.
+    pub column: usize,
+    pub message: String,
+}
+
+/// Lexical token of the command language.
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Number(String),
+    Text(String),
+    OpenBrace,
+    CloseBrace,
+    Newline,
+}
+
+/// Token with the position it starts at.
+struct Spanned {
+    token: Token,
+    line: usize,
+    column: usize,
+}
+
+/// Recursive descent parser over the tokens of a command text.
+struct Parser {
+    tokens: Vec<Spanned>,
+    index: usize,
+    /// Position reported for errors at the end of the text.
+    end: (usize, usize),
+}
+
+/// Parses a command text.
+///
+/// The language has one command per line:
+/// - `<kind> <direction> [times]`: `walk left 5`, `run up`
+/// - `face <direction>`, `wait <seconds>`, `stop`
+/// - `say to <name> "message"`, `follow <name> [distance]`, `goto <x> <y>`
+/// - `pickup`, `drop <item> [count]`, `give <item> [count] to <name>`, `use <item>`
+/// - `attack <name>`, while `attack <direction>` is a single action
+/// - `repeat <count> { ... }`, blocks may span several lines
+/// - `propose_quest "title" { ... }`, one objective per line among `reach <x> <y>`,
+///   `collect <item> [count]` and `talk to <name>`
+/// - `#` starts a comment running until the end of the line
+///
+/// # Parameters
+/// - `text`: The command text, usually a model answer.
+///
+/// # Returns
+/// The commands in execution order, or the first error found.
+pub fn parse(text: &str) -> Result<Vec<Command>, ParseError> {
+    let tokens = tokenize(text)?;
+    let line_count = text.lines().count().max(1);
+    let last_column = text.lines().last().map_or(0, |line| line.chars().count());
+    let mut parser = Parser {
+        tokens,
+        index: 0,
+        end: (line_count, last_column + 1),
+    };
+    parser.block(false)
+}
+
+/// Splits a command text into tokens.
+fn tokenize(text: &str) -> Result<Vec<Spanned>, ParseError> {
+    let mut tokens = Vec::new();
+
+    for (line_index, line) in text.lines().enumerate() {
+        let line_number = line_index + 1;
+        let mut chars = line.char_indices().peekable();
+        let column_of = |byte: usize| line[..byte].chars().count() + 1;
+
+        while let Some((start, c)) = chars.next() {
+            let column = column_of(start);
+            let token = match c {
+                '#' => break,
+                c if c.is_whitespace() => continue,
+                '{' => Token::OpenBrace,
+                '}' => Token::CloseBrace,
+                '"' => {
+                    let mut text = String::new();
+                    let mut closed = false;
+                    while let Some((_, c)) = chars.next() {
+                        match c {
+                            '"' => {
+                                closed = true;
+                                break;
+                            }
+                            '\\' => {
+                                if let Some((_, escaped)) = chars.next() {
+                                    text.push(escaped);
+                                }
+                            }
+                            c => text.push(c),
+                        }
+                    }
+                    if !closed {
+                        return Err(ParseError::new(line_number, column, "unterminated string"));
+                    }
+                    Token::Text(text)
+                }
+                c if c.is_ascii_digit() || c == '-' || c == '.' => {
+                    let mut end = start + c.len_utf8();
+                    while let Some((index, c)) =
+                        chars.next_if(|(_, c)| c.is_ascii_digit() || *c == '.')
+                    {
+                        end = index + c.len_utf8();
+                    }
+                    Token::Number(line[start..end].to_string())
+                }
+                c if c.is_alphanumeric() || c == '_' => {
+                    // Hyphens join words, as in `up-left`, but still start numbers after them
+                    let is_word_char = |(index, c): &(usize, char)| {
+                        c.is_alphanumeric()
+                            || *c == '_'
+                            || *c == '\''
+                            || (*c == '-' && line[index + 1..].starts_with(char::is_alphabetic))
+                    };
+                    let mut end = start + c.len_utf8();
+                    while let Some((index, c)) = chars.next_if(is_word_char) {
+                        end = index + c.len_utf8();
+                    }
+                    Token::Word(line[start..end].to_string())
+                }
+                c => {
+                    return Err(ParseError::new(
+                        line_number,
+                        column,
+                        format!("unexpected character '{}'", c),
+                    ))
+                }
+            };
+            tokens.push(Spanned {
+                token,
+                line: line_number,
+                column,
+            });
+        }
+
+        tokens.push(Spanned {
+            token: Token::Newline,
+            line: line_number,
+            column: line.chars().count() + 1,
+        });
+    }
+
+    Ok(tokens)
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Word(word) => write!(f, "'{}'", word),
+            Token::Number(number) => write!(f, "number {}", number),
+            Token::Text(_) => write!(f, "string"),
+            Token::OpenBrace => write!(f, "'{{'"),
+            Token::CloseBrace => write!(f, "'}}'"),
+            Token::Newline => write!(f, "end of line"),
+        }
+    }
+}
+
+impl Parser {
+    /// Peeks at the next token.
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.index).map(|spanned| &spanned.token)
+    }
+
+    /// Position of the next token, or of the end of the text.
+    fn position(&self) -> (usize, usize) {
+        self.tokens
+            .get(self.index)
+            .map_or(self.end, |spanned| (spanned.line, spanned.column))
+    }
+
+    /// Creates an error at the next token.
+    fn error(&self, message: impl Into<String>) -> ParseError {
+        let (line, column) = self.position();
+        ParseError::new(line, column, message)
+    }
+
+    /// Creates an error describing what was expected instead of the next token.
+    fn expected(&self, what: &str) -> ParseError {
+        match self.peek() {
+            Some(token) => self.error(format!("expected {}, found {}", what, token)),
+            None => self.error(format!("expected {}, found end of text", what)),
+        }
+    }
+
+    fn advance(&mut self) -> Option<Token> {
+        let token = self.peek().cloned();
+        self.index += 1;
+        token
+    }
+
+    /// Parses commands until the end of the text or of the block.
+    fn block(&mut self, nested: bool) -> Result<Vec<Command>, ParseError> {
+        let mut commands = Vec::new();
+
+        loop {
+            let position = self.position();
+            match self.peek() {
+                Some(Token::Newline) => {
+                    self.advance();
+                    continue;
+                }
+                Some(Token::CloseBrace) if nested => {
+                    self.advance();
+                    return Ok(commands);
+                }
+                Some(Token::CloseBrace) => return Err(self.error("unexpected '}'")),
+                None if nested => return Err(self.error("missing '}'")),
+                None => return Ok(commands),
+                Some(_) => {}
+            }
+
+            commands.extend(self.statement()?);
+            if commands.len() > COMMAND_MAX_COUNT {
+                return Err(ParseError::new(
+                    position.0,
+                    position.1,
+                    format!("too many commands, at most {}", COMMAND_MAX_COUNT),
+                ));
+            }
+
+            match self.peek() {
+                Some(Token::Newline | Token::CloseBrace) | None => {}
+                _ => return Err(self.expected("end of line")),
+            }
+        }
+    }
+
+    /// Parses a single command.
+    fn statement(&mut self) -> Result<Vec<Command>, ParseError> {
+        let keyword = self.word("a command")?;
+
+        match keyword.to_lowercase().as_str() {
+            "wait" => {
+                let seconds: f32 = self.number("a duration in seconds")?;
+                if !(0. ..=COMMAND_MAX_WAIT).contains(&seconds) {
+                    return Err(self.error_before(format!(
+                        "duration must be between 0 and {} seconds",
+                        COMMAND_MAX_WAIT
+                    )));
+                }
+                Ok(vec![Command::Wait(seconds)])
+            }
+            "face" => {
+                let direction = self.direction()?;
+                Ok(vec![Command::Act(Action::new(
+                    ActionKind::Stand,
+                    direction,
+                ))])
+            }
+            "repeat" => {
+                let count: usize = self.number("a repetition count")?;
+                if !(1..=COMMAND_MAX_REPEAT).contains(&count) {
+                    return Err(self.error_before(format!(
+                        "repetition count must be between 1 and {}",
+                        COMMAND_MAX_REPEAT
+                    )));
+                }
+                if self.advance() != Some(Token::OpenBrace) {
+                    self.index -= 1;
+                    return Err(self.expected("'{'"));
+                }
+                let body = self.block(true)?;
+                Ok((0..count).flat_map(|_| body.clone()).collect())
+            }
+            "say" => {
+                if matches!(self.peek(), Some(Token::Word(word)) if word.eq_ignore_ascii_case("to"))
+                {
+                    self.advance();
+                }
+                let target = self.word("a name")?;
+                let message = match self.advance() {
+                    Some(Token::Text(message)) if !message.trim().is_empty() => message,
+                    _ => {
+                        self.index -= 1;
+                        return Err(self.expected("a quoted message"));
+                    }
+                };
+                Ok(vec![Command::SayTo {
+                    target,
+                    message: message.trim().to_string(),
+                }])
+            }
+            "follow" => {
+                let target = self.word("a name")?;
+                let distance = if matches!(self.peek(), Some(Token::Number(_))) {
+                    let distance: i32 = self.number("a distance in tiles")?;
+                    if distance < 0 {
+                        return Err(self.error_before("distance must be positive"));
+                    }
+                    distance
+                } else {
+                    FOLLOW_DEFAULT_DISTANCE
+                };
+                Ok(vec![Command::Follow(FollowTarget { target, distance })])
+            }
+            "stop" => Ok(vec![Command::Stop]),
+            "pickup" => Ok(vec![Command::Item(ItemAction::PickUp)]),
+            "drop" => {
+                let item = self.item()?;
+                let count = self.count()?;
+                Ok(vec![Command::Item(ItemAction::Drop {
+                    item: Some(item),
+                    count,
+                })])
+            }
+            "give" => {
+                let item = self.item()?;
+                let count = self.count()?;
+                if !matches!(self.peek(), Some(Token::Word(word)) if word.eq_ignore_ascii_case("to"))
+                {
+                    return Err(self.expected("'to'"));
+                }
+                self.advance();
+                let target = self.word("a name")?;
+                Ok(vec![Command::Item(ItemAction::Give {
+                    item: Some(item),
+                    count,
+                    target: Some(target),
+                })])
+            }
+            "use" => {
+                let item = self.item()?;
+                Ok(vec![Command::Item(ItemAction::Use { item: Some(item) })])
+            }
+            // Not followed by a direction, the attack keeps going until the target is gone
+            "attack" if !matches!(self.peek(), Some(Token::Word(word)) if ActionDirection::from_str(word).is_ok()) =>
+            {
+                let target = self.word("a name or a direction")?;
+                Ok(vec![Command::Attack(AttackTarget { target })])
+            }
+            "propose_quest" => {
+                let position = self.position();
+                let title = match self.advance() {
+                    Some(Token::Text(title)) if !title.trim().is_empty() => title,
+                    _ => {
+                        self.index -= 1;
+                        return Err(self.expected("a quoted title"));
+                    }
+                };
+                if self.advance() != Some(Token::OpenBrace) {
+                    self.index -= 1;
+                    return Err(self.expected("'{'"));
+                }
+
+                let mut objectives = Vec::new();
+                loop {
+                    match self.peek() {
+                        Some(Token::Newline) => {
+                            self.advance();
+                        }
+                        Some(Token::CloseBrace) => {
+                            self.advance();
+                            break;
+                        }
+                        None => return Err(self.error("missing '}'")),
+                        Some(_) => {
+                            objectives.push(self.objective()?);
+                            if !matches!(self.peek(), Some(Token::Newline | Token::CloseBrace)) {
+                                return Err(self.expected("end of line"));
+                            }
+                        }
+                    }
+                }
+                if !(1..=QUEST_MAX_OBJECTIVES).contains(&objectives.len()) {
+                    return Err(ParseError::new(
+                        position.0,
+                        position.1,
+                        format!(
+                            "a quest needs between 1 and {} objectives",
+                            QUEST_MAX_OBJECTIVES
+                        ),
+                    ));
+                }
+                Ok(vec![Command::ProposeQuest(QuestProposal {
+                    title: title.trim().to_string(),
+                    objectives,
+                })])
+            }
+            "goto" => {
+                let x = self.number("an horizontal offset")?;
+                let y = self.number("a vertical offset")?;
+                Ok(vec![Command::GoTo(IVec2::new(x, y))])
+            }
+            other => {
+                let Some(kind) = ActionKind::from_str(other)
+                    .ok()
+                    .filter(|kind| COMMAND_ACTION_KINDS.contains(kind))
+                else {
+                    self.index -= 1;
+                    return Err(self.error(format!("unknown command '{}'", keyword)));
+                };
+                let direction = self.direction()?;
+                let times = if matches!(self.peek(), Some(Token::Number(_))) {
+                    let times: usize = self.number("a number of times")?;
+                    if !(1..=COMMAND_MAX_TIMES).contains(&times) {
+                        return Err(self.error_before(format!(
+                            "number of times must be between 1 and {}",
+                            COMMAND_MAX_TIMES
+                        )));
+                    }
+                    times
+                } else {
+                    1
+                };
+                Ok(vec![Command::Act(Action::new(kind, direction)); times])
+            }
+        }
+    }
+
+    /// Parses a single quest objective.
+    fn objective(&mut self) -> Result<Objective, ParseError> {
+        let keyword = self.word("an objective")?;
+
+        match keyword.to_lowercase().as_str() {
+            "reach" => {
+                let x = self.number("an horizontal offset")?;
+                let y = self.number("a vertical offset")?;
+                Ok(Objective::Reach(IVec2::new(x, y)))
+            }
+            "collect" => {
+                let item = self.item()?;
+                let count = self.count()?;
+                if count > QUEST_MAX_ITEMS {
+                    return Err(self.error_before(format!(
+                        "number of items must be at most {}",
+                        QUEST_MAX_ITEMS
+                    )));
+                }
+                Ok(Objective::Collect { item, count })
+            }
+            "talk" => {
+                if matches!(self.peek(), Some(Token::Word(word)) if word.eq_ignore_ascii_case("to"))
+                {
+                    self.advance();
+                }
+                let target = self.word("a name")?;
+                Ok(Objective::TalkTo(target))
+            }
+            _ => Err(self.error_before(format!("unknown objective '{}'", keyword))),
+        }
+    }
+
+    /// Creates an error at the previous token.
+    fn error_before(&mut self, message: impl Into<String>) -> ParseError {
+        self.index -= 1;
+        self.error(message)
+    }
+
+    /// Consumes a word.
+    fn word(&mut self, what: &str) -> Result<String, ParseError> {
+        match self.peek() {
+            Some(Token::Word(word)) => {
+                let word = word.clone();
+                self.advance();
+                Ok(word)
+            }
+            _ => Err(self.expected(what)),
+        }
+    }
+
+    /// Consumes a number.
+    fn number<T: FromStr>(&mut self, what: &str) -> Result<T, ParseError> {
+        match self.peek() {
+            Some(Token::Number(number)) => {
+                let value = number
+                    .parse()
+                    .map_err(|_| self.error(format!("invalid number {}", number)))?;
+                self.advance();
+                Ok(value)
+            }
+            _ => Err(self.expected(what)),
+        }
+    }
+
+    /// Consumes an item name, giving the id of its definition.
+    fn item(&mut self) -> Result<String, ParseError> {
+        match self.peek() {
+            Some(Token::Word(word)) => {
+                let definition = ITEM_REGISTRY
+                    .find(word)
+                    .ok_or_else(|| self.error(format!("unknown item '{}'", word)))?;
+                self.advance();
+                Ok(definition.id.to_string())
+            }
+            _ => Err(self.expected("an item")),
+        }
+    }
+
+    /// Consumes an optional item count, 1 when not given.
+    fn count(&mut self) -> Result<u32, ParseError> {
+        if !matches!(self.peek(), Some(Token::Number(_))) {
+            return Ok(1);
+        }
+        let count: u32 = self.number("a number of items")?;
+        if count == 0 {
+            return Err(self.error_before("number of items must be positive"));
+        }
+        Ok(count)
+    }
+
+    /// Consumes a direction.
+    fn direction(&mut self) -> Result<ActionDirection, ParseError> {
+        match self.peek() {
+            Some(Token::Word(word)) => {
+                let direction = ActionDirection::from_str(word)
+                    .map_err(|_| self.error(format!("unknown direction '{}'", word)))?;
+                self.advance();
+                Ok(direction)
+            }
+            _ => Err(self.expected("a direction")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn act(kind: ActionKind, direction: ActionDirection) -> Command {
+        Command::Act(Action::new(kind, direction))
+    }
+
+    fn error_at(text: &str) -> (usize, usize) {
+        let error = parse(text).expect_err(text);
+        (error.line, error.column)
+    }
+
+    #[test]
+    fn parses_one_command_per_line() {
+        let commands = parse("walk left 2\nwait 1.5\nface up\nsay to Bob \"Hello there\"\n");
+        assert_eq!(
+            commands,
+            Ok(vec![
+                act(ActionKind::Walk, ActionDirection::Left),
+                act(ActionKind::Walk, ActionDirection::Left),
+                Command::Wait(1.5),
+                act(ActionKind::Stand, ActionDirection::Up),
+                Command::SayTo {
+                    target: "Bob".to_string(),
+                    message: "Hello there".to_string(),
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn parses_hyphenated_diagonals() {
+        assert_eq!(
+            parse("walk up-left 2"),
+            Ok(vec![act(ActionKind::Walk, ActionDirection::UpLeft); 2])
+        );
+        assert_eq!(
+            parse("goto 3 -2\nrun down_right"),
+            Ok(vec![
+                Command::GoTo(IVec2::new(3, -2)),
+                act(ActionKind::Run, ActionDirection::DownRight),
+            ])
+        );
+    }
+
+    #[test]
+    fn ignores_comments_and_blank_lines() {
+        let commands = parse("# Going home\n\n  run down # quickly\n\n");
+        assert_eq!(
+            commands,
+            Ok(vec![act(ActionKind::Run, ActionDirection::Down)])
+        );
+    }
+
+    #[test]
+    fn expands_repeat_blocks() {
+        let commands = parse("repeat 2 { walk left\nwait 1 }");
+        assert_eq!(
+            commands,
+            Ok(vec![
+                act(ActionKind::Walk, ActionDirection::Left),
+                Command::Wait(1.),
+                act(ActionKind::Walk, ActionDirection::Left),
+                Command::Wait(1.),
+            ])
+        );
+    }
+
+    #[test]
+    fn expands_nested_repeat_blocks() {
+        let commands = parse("repeat 2 {\n  repeat 3 { walk up }\n  run down\n}").unwrap();
+        let round = [
+            vec![act(ActionKind::Walk, ActionDirection::Up); 3],
+            vec![act(ActionKind::Run, ActionDirection::Down)],
+        ]
+        .concat();
+        assert_eq!(commands, [round.clone(), round].concat());
+    }
+
+    #[test]
+    fn limits_the_number_of_commands() {
+        let at_most = "wait 1\n".repeat(COMMAND_MAX_COUNT);
+        assert_eq!(
+            parse(&at_most).map(|commands| commands.len()),
+            Ok(COMMAND_MAX_COUNT)
+        );
+
+        let too_many = "wait 1\n".repeat(COMMAND_MAX_COUNT + 1);
+        assert_eq!(error_at(&too_many), (COMMAND_MAX_COUNT + 1, 1));
+
+        // Expanded repetitions count, the error points at the repeat
+        let expanded = format!(
+            "wait 1\nrepeat {} {{ walk left {} }}",
+            COMMAND_MAX_REPEAT, COMMAND_MAX_TIMES
+        );
+        assert_eq!(error_at(&expanded), (2, 1));
+    }
+
+    #[test]
+    fn reports_error_positions() {
+        assert_eq!(error_at("walk left\nwalk sideways"), (2, 6));
+        assert_eq!(error_at("jump up"), (1, 1));
+        assert_eq!(error_at("walk up\nknockback left"), (2, 1));
+        assert_eq!(error_at("swim down"), (1, 1));
+        assert_eq!(error_at("wait 99"), (1, 6));
+        assert_eq!(error_at("walk left }"), (1, 11));
+        assert_eq!(error_at("walk left up"), (1, 11));
+        assert_eq!(error_at("say to Bob \"Hello"), (1, 12));
+        assert_eq!(error_at("repeat 2 { walk left"), (1, 21));
+        assert_eq!(error_at("walk left\n  wait @"), (2, 8));
+    }
+
+    #[test]
+    fn describes_errors_with_their_position() {
+        let error = parse("walk left\nwalk sideways").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 6: unknown direction 'sideways'"
+        );
+    }
+
+    #[test]
+    fn attacks_a_direction_or_a_target() {
+        assert_eq!(
+            parse("attack left"),
+            Ok(vec![act(ActionKind::Attack, ActionDirection::Left)])
+        );
+        assert_eq!(
+            parse("attack down-right"),
+            Ok(vec![act(ActionKind::Attack, ActionDirection::DownRight)])
+        );
+        assert_eq!(
+            parse("attack UpLeft"),
+            Ok(vec![act(ActionKind::Attack, ActionDirection::UpLeft)])
+        );
+        assert_eq!(
+            parse("attack Wolf"),
+            Ok(vec![Command::Attack(AttackTarget {
+                target: "Wolf".to_string(),
+            })])
+        );
+        assert_eq!(error_at("attack"), (1, 7));
+    }
+}
@@ -1,6 +1,8 @@
 use bevy::prelude::*;
 use bevy_ecs_tilemap::tiles::TilePos;
 
+use crate::components::action::{ActionTimer, Motion};
+use crate::constants::structure::REGION_SIZE;
 use crate::{components::texture::TilesetOffset, constants::map::*};
 
 /// Converts a pixel position to chunk position.
@@ -43,6 +45,38 @@ pub fn player_tile_pos(tranform: &Transform, offset: &TilesetOffset) -> IVec2 {
     pixel_pos_to_tile_pos(&(tranform.translation.xy() - **offset))
 }
 
+/// Calculates the nearest tile position of a free moving player.
+///
+/// # Parameters
+/// - `transform`: The transform component of the player.
+/// - `offset`: The tileset offset for the player.
+///
+/// # Returns
+/// The nearest tile position as `IVec2`.
+pub fn player_nearest_tile_pos(tranform: &Transform, offset: &TilesetOffset) -> IVec2 {
+    pixel_pos_to_nearest_tile_pos(&(tranform.translation.xy() - **offset))
+}
+
+/// Calculates the tile position an entity started its current action from.
+///
+/// # Parameters
+/// - `transform`: The transform component of the entity.
+/// - `offset`: The tileset offset for the entity.
+/// - `motion`: The fixed-timestep movement of the entity.
+/// - `timer`: The timer of the current action.
+///
+/// # Returns
+/// The tile position as `IVec2`.
+pub fn action_start_tile_pos(
+    transform: &Transform,
+    offset: &TilesetOffset,
+    motion: &Motion,
+    timer: &ActionTimer,
+) -> IVec2 {
+    let start = Transform::from_translation(motion.start_translation(transform, timer));
+    player_tile_pos(&start, offset)
+}
+
 /// Converts a pixel position to a tile position.
 ///
 /// # Parameters
@@ -54,6 +88,20 @@ pub fn pixel_pos_to_tile_pos(pixel_pos: &Vec2) -> IVec2 {
     (*pixel_pos * 1. / TILE).as_ivec2()
 }
 
+/// Converts a pixel position to the position of the nearest tile.
+///
+/// Free moving characters are between tiles most of the time, they are on
+/// the tile they overlap the most rather than the one truncation gives.
+///
+/// # Parameters
+/// - `pixel_pos`: The pixel position to be converted.
+///
+/// # Returns
+/// The nearest tile position as `IVec2`.
+pub fn pixel_pos_to_nearest_tile_pos(pixel_pos: &Vec2) -> IVec2 {
+    (*pixel_pos * 1. / TILE).round().as_ivec2()
+}
+
 /// Converts a tile position to a chunk position.
 ///
 /// # Parameters
@@ -79,3 +127,58 @@ pub fn relative_tile_pos(tile_pos: &IVec2) -> TilePos {
         y: position.y,
     }
 }
+
+/// Converts a tile position to the position of its generation region.
+///
+/// # Parameters
+/// - `tile_pos`: The tile position to be converted.
+///
+/// # Returns
+/// The region position as `IVec2`.
+pub fn tile_pos_to_region_pos(tile_pos: &IVec2) -> IVec2 {
+    tile_pos.div_euclid(IVec2::splat(REGION_SIZE))
+}
+
+/// Names the compass direction closest to a direction.
+///
+/// # Parameters
+/// - `direction`: The direction, north being up.
+///
+/// # Returns
+/// One of "east", "west", "north" or "south".
+pub fn describe_direction(direction: IVec2) -> &'static str {
+    if direction.x.abs() >= direction.y.abs() {
+        if direction.x > 0 {
+            "east"
+        } else {
+            "west"
+        }
+    } else if direction.y > 0 {
+        "north"
+    } else {
+        "south"
+    }
+}
+
+/// Describes an offset in tiles with compass directions.
+///
+/// # Parameters
+/// - `offset`: The offset, north being up.
+///
+/// # Returns
+/// A description like "20 tiles east and 3 tiles north".
+pub fn describe_offset(offset: IVec2) -> String {
+    let parts = [
+        (offset.x, if offset.x > 0 { "east" } else { "west" }),
+        (offset.y, if offset.y > 0 { "north" } else { "south" }),
+    ]
+    .into_iter()
+    .filter(|(distance, _)| *distance != 0)
+    .map(|(distance, direction)| format!("{} tiles {}", distance.abs(), direction))
+    .collect::<Vec<_>>();
+    if parts.is_empty() {
+        String::from("right here")
+    } else {
+        parts.join(" and ")
+    }
+}
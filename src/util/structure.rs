@@ -0,0 +1,116 @@
+use bevy::math::IVec2;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+use crate::components::structure::{StructureKind, StructurePiece, StructureTemplate};
+use crate::constants::generation::WATER_LEVEL;
+use crate::constants::structure::*;
+use crate::util::noise::{hash_position, NOISE};
+
+/// A structure placed in a generation region.
+pub struct PlacedStructure {
+    pub template: &'static StructureTemplate,
+    pub name: &'static str,
+    /// Tile of the top left corner.
+    pub origin: IVec2,
+}
+
+impl PlacedStructure {
+    /// Tile at the center of the structure.
+    pub fn center(&self) -> IVec2 {
+        let width = self.template.rows[0].len() as i32;
+        let height = self.template.rows.len() as i32;
+        self.origin + IVec2::new(width / 2, -(height / 2))
+    }
+
+    /// Lists the tiles of the structure with their template character.
+    pub fn tiles(&self) -> impl Iterator<Item = (IVec2, char)> + '_ {
+        self.template
+            .rows
+            .iter()
+            .enumerate()
+            .flat_map(move |(row, line)| {
+                line.chars().enumerate().map(move |(column, tile)| {
+                    (self.origin + IVec2::new(column as i32, -(row as i32)), tile)
+                })
+            })
+    }
+
+    /// Lists the pieces of the structure with their tile positions.
+    pub fn pieces(&self) -> impl Iterator<Item = (IVec2, StructurePiece)> + '_ {
+        self.tiles().filter_map(|(tile_pos, tile)| {
+            StructurePiece::from_char(tile).map(|piece| (tile_pos, piece))
+        })
+    }
+}
+
+/// Finds the structure of a generation region.
+///
+/// The structure only depends on the region position and the relief noise,
+/// so every chunk of the region agrees on it whatever the loading order.
+///
+/// # Parameters
+/// - `region`: The region position, see `tile_pos_to_region_pos`.
+///
+/// # Returns
+/// The structure, or `None` if the region has none or the land does not fit.
+pub fn structure_in_region(region: IVec2) -> Option<PlacedStructure> {
+    let mut rng = StdRng::seed_from_u64(hash_position(STRUCTURE_SEED, region));
+    let spawns = rng.gen_bool(STRUCTURE_CHANCE);
+    let template = pick_template(&mut rng);
+    let name = template.names.choose(&mut rng)?;
+    let width = template.rows[0].len() as i32;
+    let height = template.rows.len() as i32;
+    let base = region * REGION_SIZE;
+    let origin = base
+        + IVec2::new(
+            rng.gen_range(0..=REGION_SIZE - width),
+            height - 1 + rng.gen_range(0..=REGION_SIZE - height),
+        );
+    if !spawns {
+        return None;
+    }
+
+    let structure = PlacedStructure {
+        template,
+        name,
+        origin,
+    };
+    let center = structure.center();
+    let level = NOISE.get_value(center.x, center.y);
+    let fits = structure
+        .tiles()
+        .filter(|(_, tile)| *tile != ' ')
+        .all(|(tile_pos, _)| {
+            // Away from the spawn, on flat and dry land
+            tile_pos.abs().max_element() > STRUCTURE_SPAWN_CLEARANCE
+                && level != WATER_LEVEL
+                && NOISE.get_value(tile_pos.x, tile_pos.y) == level
+                && NOISE.get_mask(level, tile_pos.x, tile_pos.y) == 0
+        });
+    fits.then_some(structure)
+}
+
+/// Finds the closest structure of the regions around a region, signposts excluded.
+///
+/// # Parameters
+/// - `region`: The region in the middle.
+/// - `from`: The tile the distances are measured from.
+pub fn closest_structure(region: IVec2, from: IVec2) -> Option<PlacedStructure> {
+    (-1..=1)
+        .flat_map(|y| (-1..=1).map(move |x| region + IVec2::new(x, y)))
+        .filter_map(structure_in_region)
+        .filter(|structure| structure.template.kind != StructureKind::Signpost)
+        .min_by_key(|structure| (structure.center() - from).abs().max_element())
+}
+
+/// Picks the template of a structure.
+///
+/// # Parameters
+/// - `rng`: The random number generator of the region.
+fn pick_template(rng: &mut StdRng) -> &'static StructureTemplate {
+    WeightedIndex::new(STRUCTURES.iter().map(|template| template.weight))
+        .map_or(&STRUCTURES[0], |dist| &STRUCTURES[dist.sample(rng)])
+}